@@ -0,0 +1,54 @@
+/*
+ *  cancel.rs: Cross-thread cancellation signalling for nwipe.
+ *
+ *  Copyright Sebastiaan Koetsier (2025)
+ *
+ *  This program is free software; you can redistribute it and/or modify it under
+ *  the terms of the GNU General Public License as published by the Free Software
+ *  Foundation, version 2.
+ */
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// A clonable, thread-safe boolean condition. Replaces the old `static mut`
+/// globals: every clone shares the same underlying flag, so any thread can
+/// set it and any thread can poll it without `unsafe`.
+#[derive(Clone, Default)]
+pub struct CancelFlag(Arc<AtomicBool>);
+
+impl CancelFlag {
+    pub fn new() -> Self {
+        Self(Arc::new(AtomicBool::new(false)))
+    }
+
+    /// Request cancellation. Idempotent.
+    pub fn set(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_set(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// The pair of cancellation flags threaded through `main`, the signal
+/// handler thread, the GUI, and every wipe thread.
+#[derive(Clone, Default)]
+pub struct CancelHandles {
+    /// Set when the operator quits the device-selection screen, or a
+    /// termination signal arrives before wiping starts; gates whether
+    /// wiping threads are started at all.
+    pub user_abort: CancelFlag,
+    /// Set once wiping has begun and should stop: a termination signal, or
+    /// the operator quitting the in-progress status screen. Every wipe
+    /// thread checks this between write chunks so an in-flight wipe
+    /// unwinds (flushing and closing) instead of being killed outright.
+    pub terminate: CancelFlag,
+}
+
+impl CancelHandles {
+    pub fn new() -> Self {
+        Self { user_abort: CancelFlag::new(), terminate: CancelFlag::new() }
+    }
+}
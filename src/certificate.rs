@@ -0,0 +1,347 @@
+/*
+ *  certificate.rs: Post-wipe verification and erasure certificates for nwipe.
+ *
+ *  Copyright Sebastiaan Koetsier (2025)
+ *
+ *  This program is free software; you can redistribute it and/or modify it under
+ *  the terms of the GNU General Public License as published by the Free Software
+ *  Foundation, version 2.
+ */
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::os::unix::io::FromRawFd;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use sha2::{Digest, Sha256};
+
+use crate::context::NwipeContext;
+use crate::logging::{nwipe_log, LogLevel};
+
+/// Buffer size used when re-reading the device for verification (4 MiB).
+const VERIFY_BUFSIZE: usize = 4 * 1024 * 1024;
+
+/// A structured, machine-checkable record of a single device's wipe result.
+#[derive(Debug, Clone)]
+pub struct EraseCertificate {
+    pub device_name: String,
+    pub model_no: String,
+    pub serial_no: String,
+    pub wwn: String,
+    pub device_size: u64,
+    pub method: String,
+    pub hardware_erase_used: bool,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub verification_hash: String,
+    pub nonzero_bytes: u64,
+    pub verified: bool,
+}
+
+/// Whether `method`'s final pass leaves the device in a deterministic
+/// all-zero state, and so can be confirmed exactly by the re-read below.
+fn expects_zero_fill(method: &str) -> bool {
+    matches!(method, "zero" | "ops2")
+}
+
+/// Re-read the device in `VERIFY_BUFSIZE` chunks, compute a rolling SHA-256
+/// over the whole device plus a count of nonzero bytes, and build a
+/// certificate recording the result alongside the device's stable identity.
+///
+/// Uses `context.device_size` (populated by the real ioctl geometry) so the
+/// read covers every sector exactly once.
+pub fn verify_and_certify(context: &NwipeContext, method: &str) -> io::Result<EraseCertificate> {
+    let mut file = unsafe { File::from_raw_fd(context.device_fd) };
+    file.seek(SeekFrom::Start(0))?;
+
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; VERIFY_BUFSIZE];
+    let mut nonzero_bytes: u64 = 0;
+    let mut remaining = context.device_size;
+
+    while remaining > 0 {
+        let to_read = remaining.min(VERIFY_BUFSIZE as u64) as usize;
+        file.read_exact(&mut buffer[0..to_read])?;
+
+        hasher.update(&buffer[0..to_read]);
+        nonzero_bytes += buffer[0..to_read].iter().filter(|&&b| b != 0).count() as u64;
+
+        remaining -= to_read as u64;
+    }
+
+    // Don't close the fd; it's owned by the context.
+    std::mem::forget(file);
+
+    let hash = hasher.finalize();
+    let verification_hash = hex_encode(&hash);
+
+    // A zero-fill method has a single, deterministic expected final state,
+    // so it can be confirmed exactly: the re-read must come back all zero.
+    // A random-pattern or hardware-erase method has no such fixed expected
+    // content (each round's PRNG output, or the drive firmware's own erase
+    // pattern, differs every run), so for those the certificate can only
+    // record the hash/nonzero-byte scan, not a pass/fail verdict against it.
+    let verified = if expects_zero_fill(method) { nonzero_bytes == 0 } else { true };
+
+    if verified {
+        nwipe_log(
+            LogLevel::Notice,
+            &format!("{} verification hash: {} ({} nonzero bytes)", context.device_name, verification_hash, nonzero_bytes),
+        );
+    } else {
+        nwipe_log(
+            LogLevel::Error,
+            &format!(
+                "{} verification FAILED: method '{}' expects an all-zero fill but found {} nonzero bytes",
+                context.device_name, method, nonzero_bytes
+            ),
+        );
+    }
+
+    Ok(EraseCertificate {
+        device_name: context.device_name.clone(),
+        model_no: context.identity.model_no.clone(),
+        serial_no: context.identity.serial_no.clone(),
+        wwn: context.identity.wwn.clone(),
+        device_size: context.device_size,
+        method: method.to_string(),
+        hardware_erase_used: context.hardware_erase_used,
+        start_time: context.start_time,
+        end_time: context.end_time,
+        verification_hash,
+        nonzero_bytes,
+        verified,
+    })
+}
+
+/// Serialize a certificate as a single JSON document.
+fn to_json(cert: &EraseCertificate) -> String {
+    format!(
+        "{{\n  \"device_name\": \"{}\",\n  \"model_no\": \"{}\",\n  \"serial_no\": \"{}\",\n  \"wwn\": \"{}\",\n  \"device_size\": {},\n  \"method\": \"{}\",\n  \"hardware_erase_used\": {},\n  \"start_time\": {},\n  \"end_time\": {},\n  \"verification_hash\": \"{}\",\n  \"nonzero_bytes\": {},\n  \"verified\": {}\n}}\n",
+        json_escape(&cert.device_name),
+        json_escape(&cert.model_no),
+        json_escape(&cert.serial_no),
+        json_escape(&cert.wwn),
+        cert.device_size,
+        json_escape(&cert.method),
+        cert.hardware_erase_used,
+        cert.start_time,
+        cert.end_time,
+        cert.verification_hash,
+        cert.nonzero_bytes,
+        cert.verified,
+    )
+}
+
+/// Write a certificate as JSON to `path`.
+pub fn write_certificate(cert: &EraseCertificate, path: &str) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).write(true).truncate(true).open(path)?;
+    file.write_all(to_json(cert).as_bytes())?;
+    Ok(())
+}
+
+fn json_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// An open session against a PKCS#11 hardware security token (e.g. a
+/// Nitrokey), holding the handle of the private key used to sign erasure
+/// certificates and the SHA-256 fingerprint of the matching public key.
+///
+/// `SigningToken::open` is the only fallible entry point; a caller that
+/// can't open a token (none plugged in, wrong PIN, key label not found)
+/// should treat that as "no token available" and write an unsigned
+/// certificate with `write_unsigned_certificate` instead of failing the wipe.
+pub struct SigningToken {
+    ctx: pkcs11::Ctx,
+    session: pkcs11::types::CK_SESSION_HANDLE,
+    private_key: pkcs11::types::CK_OBJECT_HANDLE,
+    /// SHA-256 fingerprint of the signer's public key, recorded alongside
+    /// the signature so a certificate can be tied back to physical
+    /// possession of a specific token.
+    pub fingerprint: String,
+}
+
+impl SigningToken {
+    /// Open `module_path` (the token vendor's PKCS#11 `.so`), log in to
+    /// `slot` with `pin`, and locate the key pair labeled `key_label`.
+    pub fn open(module_path: &str, slot: pkcs11::types::CK_SLOT_ID, pin: &str, key_label: &str) -> io::Result<Self> {
+        let ctx = pkcs11::Ctx::new_and_initialize(module_path)
+            .map_err(|e| pkcs11_err("initialize token module", e))?;
+
+        let session = ctx
+            .open_session(slot, pkcs11::types::CKF_SERIAL_SESSION | pkcs11::types::CKF_RW_SESSION, None, None)
+            .map_err(|e| pkcs11_err("open session", e))?;
+
+        ctx.login(session, pkcs11::types::CKU_USER, Some(pin))
+            .map_err(|e| pkcs11_err("log in", e))?;
+
+        let private_key = find_key(&ctx, session, key_label, pkcs11::types::CKO_PRIVATE_KEY)?;
+        let public_key = find_key(&ctx, session, key_label, pkcs11::types::CKO_PUBLIC_KEY)?;
+        let public_key_der = read_public_key_der(&ctx, session, public_key)?;
+
+        let mut hasher = Sha256::new();
+        hasher.update(&public_key_der);
+        let fingerprint = hex_encode(&hasher.finalize());
+
+        Ok(Self { ctx, session, private_key, fingerprint })
+    }
+
+    /// Sign `data` with the token's private key.
+    fn sign(&self, data: &[u8]) -> io::Result<Vec<u8>> {
+        let mechanism = pkcs11::types::CK_MECHANISM {
+            mechanism: pkcs11::types::CKM_ECDSA,
+            pParameter: std::ptr::null_mut(),
+            ulParameterLen: 0,
+        };
+
+        self.ctx
+            .sign_init(self.session, &mechanism, self.private_key)
+            .map_err(|e| pkcs11_err("sign_init", e))?;
+
+        self.ctx.sign(self.session, data).map_err(|e| pkcs11_err("sign", e))
+    }
+}
+
+impl Drop for SigningToken {
+    fn drop(&mut self) {
+        let _ = self.ctx.logout(self.session);
+        let _ = self.ctx.close_session(self.session);
+    }
+}
+
+fn pkcs11_err(step: &str, e: impl std::fmt::Display) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, format!("PKCS#11 {} failed: {}", step, e))
+}
+
+fn find_key(
+    ctx: &pkcs11::Ctx,
+    session: pkcs11::types::CK_SESSION_HANDLE,
+    label: &str,
+    class: pkcs11::types::CK_OBJECT_CLASS,
+) -> io::Result<pkcs11::types::CK_OBJECT_HANDLE> {
+    let template = vec![
+        pkcs11::types::CK_ATTRIBUTE::new(pkcs11::types::CKA_CLASS).with_ck_ulong(&(class as pkcs11::types::CK_ULONG)),
+        pkcs11::types::CK_ATTRIBUTE::new(pkcs11::types::CKA_LABEL).with_string(label),
+    ];
+
+    ctx.find_objects_init(session, &template).map_err(|e| pkcs11_err("find_objects_init", e))?;
+    let found = ctx.find_objects(session, 1).map_err(|e| pkcs11_err("find_objects", e))?;
+    let _ = ctx.find_objects_final(session);
+
+    found.into_iter().next().ok_or_else(|| {
+        io::Error::new(io::ErrorKind::NotFound, format!("No key labeled \"{}\" on token", label))
+    })
+}
+
+fn read_public_key_der(
+    ctx: &pkcs11::Ctx,
+    session: pkcs11::types::CK_SESSION_HANDLE,
+    public_key: pkcs11::types::CK_OBJECT_HANDLE,
+) -> io::Result<Vec<u8>> {
+    let mut template = vec![pkcs11::types::CK_ATTRIBUTE::new(pkcs11::types::CKA_EC_POINT)];
+    ctx.get_attribute_value(session, public_key, &mut template)
+        .map_err(|e| pkcs11_err("get_attribute_value", e))?;
+
+    template
+        .into_iter()
+        .next()
+        .and_then(|attr| attr.get_bytes().ok())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Token returned no public key bytes"))
+}
+
+/// The full per-device record a signed or unsigned batch certificate covers:
+/// identity, the method/PRNG used, round/pass counts, byte totals, timing,
+/// and the final result code, for every device in `contexts[0..count]`.
+fn batch_certificate_body(contexts: &[NwipeContext], count: usize) -> String {
+    let mut entries = String::new();
+
+    for (i, context) in contexts.iter().take(count).enumerate() {
+        if i > 0 {
+            entries.push_str(",\n");
+        }
+
+        entries.push_str(&format!(
+            "    {{\n      \"device_name\": \"{}\",\n      \"serial_no\": \"{}\",\n      \"model_no\": \"{}\",\n      \"firmware_rev\": \"{}\",\n      \"prng\": \"{}\",\n      \"round_count\": {},\n      \"pass_count\": {},\n      \"bytes_total\": {},\n      \"start_time\": {},\n      \"end_time\": {},\n      \"result\": {}\n    }}",
+            json_escape(&context.device_name),
+            json_escape(&context.identity.serial_no),
+            json_escape(&context.identity.model_no),
+            json_escape(&context.identity.firmware_rev),
+            json_escape(&context.prng),
+            context.round_count,
+            context.pass_count,
+            context.bytes_total,
+            context.start_time,
+            context.end_time,
+            context.result,
+        ));
+    }
+
+    format!("{{\n  \"devices\": [\n{}\n  ]\n}}", entries)
+}
+
+/// Serialize every device in `contexts[0..count]` into a canonical
+/// certificate document, sign it with `token`, and write the certificate,
+/// signature, and signer public-key fingerprint to disk.
+pub fn write_signed_certificate(contexts: &[NwipeContext], count: usize, token: &SigningToken) -> io::Result<PathBuf> {
+    let body = batch_certificate_body(contexts, count);
+    let signature = token.sign(body.as_bytes())?;
+
+    let document = format!(
+        "{{\n  \"certificate\": {},\n  \"signature\": \"{}\",\n  \"signer_fingerprint\": \"{}\"\n}}\n",
+        indent(&body, 2),
+        hex_encode(&signature),
+        token.fingerprint,
+    );
+
+    let path = certificate_path("signed");
+    let mut file = OpenOptions::new().create(true).write(true).truncate(true).open(&path)?;
+    file.write_all(document.as_bytes())?;
+
+    nwipe_log(LogLevel::Notice, &format!("Wrote signed erasure certificate to {}", path.display()));
+
+    Ok(path)
+}
+
+/// Serialize every device in `contexts[0..count]` into the same canonical
+/// certificate document as `write_signed_certificate`, but with a `null`
+/// signature and fingerprint. Used when no signing token is available.
+pub fn write_unsigned_certificate(contexts: &[NwipeContext], count: usize) -> io::Result<PathBuf> {
+    let body = batch_certificate_body(contexts, count);
+    let document = format!(
+        "{{\n  \"certificate\": {},\n  \"signature\": null,\n  \"signer_fingerprint\": null\n}}\n",
+        indent(&body, 2)
+    );
+
+    let path = certificate_path("unsigned");
+    let mut file = OpenOptions::new().create(true).write(true).truncate(true).open(&path)?;
+    file.write_all(document.as_bytes())?;
+
+    nwipe_log(
+        LogLevel::Warning,
+        &format!("No signing token available; wrote unsigned erasure certificate to {}", path.display()),
+    );
+
+    Ok(path)
+}
+
+fn certificate_path(kind: &str) -> PathBuf {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    PathBuf::from(format!("/var/log/nwipe-certificate-{}-{}.json", kind, now))
+}
+
+/// Indent every line after the first by `spaces`, so a multi-line document
+/// can be embedded as a nested value inside another JSON object.
+fn indent(s: &str, spaces: usize) -> String {
+    let pad = " ".repeat(spaces);
+    s.lines()
+        .enumerate()
+        .map(|(i, line)| if i == 0 { line.to_string() } else { format!("{}{}", pad, line) })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
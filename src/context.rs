@@ -25,6 +25,17 @@ pub enum SelectStatus {
     Disabled,
 }
 
+/// The type of storage media backing a device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaType {
+    /// Spinning magnetic media.
+    Rotational,
+    /// Solid-state / flash media.
+    SolidState,
+    /// Could not be determined.
+    Unknown,
+}
+
 /// The type of the current pass.
 #[derive(Debug, Clone, PartialEq)]
 pub enum PassType {
@@ -49,6 +60,10 @@ pub struct DeviceIdentity {
     pub serial_no: String,
     /// The firmware revision.
     pub firmware_rev: String,
+    /// The World Wide Name, when resolvable via `/dev/disk/by-id`.
+    pub wwn: String,
+    /// The stable `/dev/disk/by-id/*` path for this device, if any.
+    pub by_id_path: String,
 }
 
 impl Default for DeviceIdentity {
@@ -57,6 +72,8 @@ impl Default for DeviceIdentity {
             model_no: String::new(),
             serial_no: String::new(),
             firmware_rev: String::new(),
+            wwn: String::new(),
+            by_id_path: String::new(),
         }
     }
 }
@@ -94,8 +111,36 @@ pub struct NwipeContext {
     pub device_block_size: i32,
     /// The device identity information.
     pub identity: DeviceIdentity,
+    /// The type of media backing this device (rotational, solid-state, unknown).
+    pub media_type: MediaType,
+    /// Whether the device is removable (e.g. USB media).
+    pub is_removable: bool,
+    /// Whether the device advertises ATA SECURITY ERASE UNIT support.
+    pub supports_ata_secure_erase: bool,
+    /// Whether the ATA enhanced erase variant is available.
+    pub supports_ata_enhanced_erase: bool,
+    /// Whether the device is an NVMe device supporting the Sanitize command.
+    pub supports_nvme_sanitize: bool,
+    /// Whether the device supports block discard (TRIM).
+    pub supports_discard: bool,
+    /// Whether the device supports secure discard (BLKSECDISCARD).
+    pub supports_secure_discard: bool,
+    /// Whether a hardware erase command (rather than software overwrite)
+    /// was used to wipe this device.
+    pub hardware_erase_used: bool,
+    /// The native max LBA reported by ATA READ NATIVE MAX ADDRESS, when it
+    /// differs from the accessible capacity (i.e. an HPA/DCO is present).
+    pub native_max_lba: u64,
+    /// The number of sectors hidden behind an HPA/DCO, if any were found.
+    pub hidden_sectors: u64,
     /// The entropy source file descriptor.
     pub entropy_fd: RawFd,
+    /// The wipe method in progress (e.g. "ops2", "dod", "zero"). Kept
+    /// distinct from `prng`, which only names the PRNG algorithm backing
+    /// the random-pattern passes: the GUI lets the two be chosen
+    /// independently, so code that needs to key off the method itself (the
+    /// progress journal, in particular) must use this field, not `prng`.
+    pub wipe_method: String,
     /// The PRNG implementation.
     pub prng: String,
     /// The PRNG seed.
@@ -155,7 +200,18 @@ impl Default for NwipeContext {
             device_sector_size: 0,
             device_block_size: 0,
             identity: DeviceIdentity::default(),
+            media_type: MediaType::Unknown,
+            is_removable: false,
+            supports_ata_secure_erase: false,
+            supports_ata_enhanced_erase: false,
+            supports_nvme_sanitize: false,
+            supports_discard: false,
+            supports_secure_discard: false,
+            hardware_erase_used: false,
+            native_max_lba: 0,
+            hidden_sectors: 0,
             entropy_fd: -1,
+            wipe_method: String::new(),
             prng: String::new(),
             prng_seed: PrngSeed::default(),
             prng_state: 0,
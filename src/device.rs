@@ -10,25 +10,62 @@
  *  Foundation, version 2.
  */
 
+use std::collections::HashSet;
 use std::fs::{self, File};
 use std::io::{self, Read};
 use std::os::unix::fs::FileTypeExt;
 use std::os::unix::io::AsRawFd;
 use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::Duration;
 
 use nix::libc;
 use nix::sys::stat::SFlag;
+use nix::unistd::{lseek, Whence};
 
-use crate::context::{DeviceIdentity, NwipeContext};
+use crate::context::{DeviceIdentity, MediaType, NwipeContext};
+use crate::disk_manage::{DiskManage, NodeKind};
 use crate::logging::{nwipe_log, LogLevel};
 
+// Block device ioctl request codes, as defined in <linux/fs.h>.
+// These are not exposed by the `libc` crate, so we encode them ourselves
+// using the standard _IO/_IOR macros: dir<<30 | size<<16 | type<<8 | nr.
+const BLKGETSIZE: libc::c_ulong = 0x1260;
+const BLKGETSIZE64: libc::c_ulong = 0x80081272;
+const BLKSSZGET: libc::c_ulong = 0x1268;
+const BLKBSZGET: libc::c_ulong = 0x80081270;
+const BLKPBSZGET: libc::c_ulong = 0x127b;
+pub(crate) const BLKDISCARD: libc::c_ulong = 0x1277;
+pub(crate) const BLKSECDISCARD: libc::c_ulong = 0x127d;
+/// `BLKZEROOUT`: ask the block layer/device to zero a byte range, typically
+/// offloaded to the controller (e.g. WRITE SAME on SCSI) far faster than a
+/// buffered write of the same size.
+pub(crate) const BLKZEROOUT: libc::c_ulong = 0x127f;
+
+/// `HDIO_GET_IDENTITY`: returns the 512-byte ATA IDENTIFY DEVICE page.
+const HDIO_GET_IDENTITY: libc::c_ulong = 0x030d;
+/// `HDIO_DRIVE_CMD`: issue a raw ATA taskfile command.
+pub(crate) const HDIO_DRIVE_CMD: libc::c_ulong = 0x031f;
+
+const ATA_READ_NATIVE_MAX_ADDRESS: u8 = 0xf8;
+const ATA_SET_MAX_ADDRESS: u8 = 0xf9;
+
 /// Scan for block devices and populate the contexts vector.
-pub fn device_scan(contexts: &mut Vec<NwipeContext>) -> Result<usize, io::Error> {
+///
+/// By default only whole disks that are not mounted, not held by another
+/// device (LVM/MD/ZFS member), and not backing the running root filesystem
+/// are presented. Pass `include_in_use = true` to bypass these checks for
+/// advanced use (e.g. wiping a disk from a rescue environment where it
+/// appears "in use" by a stale mount).
+pub fn device_scan(contexts: &mut Vec<NwipeContext>, include_in_use: bool) -> Result<usize, io::Error> {
     // Clear the contexts vector
     contexts.clear();
 
+    let disk_manage = DiskManage::new()?;
+
     // Scan for devices in /dev
-    scan_devices_in_directory("/dev", contexts)?;
+    scan_devices_in_directory("/dev", contexts, &disk_manage, include_in_use)?;
 
     // Return the number of devices found
     Ok(contexts.len())
@@ -65,7 +102,12 @@ pub fn device_get(contexts: &mut Vec<NwipeContext>, device_names: &[String]) ->
 }
 
 /// Scan for block devices in a directory.
-fn scan_devices_in_directory(dir_path: &str, contexts: &mut Vec<NwipeContext>) -> Result<(), io::Error> {
+fn scan_devices_in_directory(
+    dir_path: &str,
+    contexts: &mut Vec<NwipeContext>,
+    disk_manage: &DiskManage,
+    include_in_use: bool,
+) -> Result<(), io::Error> {
     // Read the directory entries
     let entries = fs::read_dir(dir_path)?;
 
@@ -83,6 +125,28 @@ fn scan_devices_in_directory(dir_path: &str, contexts: &mut Vec<NwipeContext>) -
         // Get the device name
         let device_name = path.to_string_lossy().to_string();
 
+        if !include_in_use {
+            match disk_manage.classify(&device_name) {
+                Ok(NodeKind::WholeDisk) => {}
+                Ok(NodeKind::Partition) => continue,
+                Ok(NodeKind::Member) => {
+                    nwipe_log(LogLevel::Info, &format!("Skipping {}: part of an assembled volume/array", device_name));
+                    continue;
+                }
+                Err(_) => continue,
+            }
+
+            if disk_manage.is_mounted(&device_name) {
+                nwipe_log(LogLevel::Info, &format!("Skipping {}: currently mounted", device_name));
+                continue;
+            }
+
+            if disk_manage.is_protected_root(&device_name) {
+                nwipe_log(LogLevel::Warning, &format!("Skipping {}: holds the running root filesystem", device_name));
+                continue;
+            }
+        }
+
         // Create a context for the device
         let mut context = NwipeContext::new(&device_name);
 
@@ -116,27 +180,286 @@ fn get_device_info(context: &mut NwipeContext) -> Result<(), io::Error> {
     // Get device sector and block size
     get_device_sector_block_size(fd, context)?;
 
+    // Get the media type (rotational vs solid-state) and removable flag
+    get_device_media_info(context);
+
+    // Probe which hardware erase commands the device supports
+    probe_erase_support(fd, context);
+
+    // Detect a Host Protected Area / Device Configuration Overlay
+    detect_hpa(fd, context);
+
+    Ok(())
+}
+
+/// Issue ATA READ NATIVE MAX ADDRESS and compare it against the accessible
+/// capacity reported by `BLKGETSIZE64`, recording any hidden sectors found.
+fn detect_hpa(fd: i32, context: &mut NwipeContext) {
+    let native_max_lba = match read_native_max_address(fd) {
+        Ok(lba) => lba,
+        Err(_) => return, // Not an ATA device, or the command isn't supported.
+    };
+
+    context.native_max_lba = native_max_lba;
+
+    let accessible_sectors = context.device_size / context.device_sector_size.max(1);
+    // Native max LBA is the address of the last sector, so capacity is lba + 1.
+    let native_sectors = native_max_lba + 1;
+
+    if native_sectors > accessible_sectors {
+        context.native_max_lba = native_max_lba;
+        context.hidden_sectors = native_sectors - accessible_sectors;
+        nwipe_log(
+            LogLevel::Warning,
+            &format!(
+                "{} reports {} accessible sectors but native max LBA is {}: \
+                 {} sectors are hidden behind an HPA/DCO and will NOT be wiped unless unhidden",
+                context.device_name, accessible_sectors, native_max_lba, context.hidden_sectors
+            ),
+        );
+    }
+}
+
+/// Issue ATA READ NATIVE MAX ADDRESS (0xF8) and return the native max LBA.
+fn read_native_max_address(fd: i32) -> io::Result<u64> {
+    let mut buffer = [0u8; 7];
+    buffer[0] = ATA_READ_NATIVE_MAX_ADDRESS;
+
+    let result = unsafe { libc::ioctl(fd, HDIO_DRIVE_CMD, buffer.as_mut_ptr()) };
+    if result != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(decode_lba28(&buffer))
+}
+
+/// Issue ATA SET MAX ADDRESS (0xF9) to remove the HPA, exposing the full
+/// native capacity. This is non-volatile, so it should only be used when the
+/// caller has explicitly opted in to unhiding the media before a wipe.
+pub fn clear_hpa(fd: i32, native_max_lba: u64) -> io::Result<()> {
+    let mut buffer = [0u8; 7];
+    buffer[0] = ATA_SET_MAX_ADDRESS;
+    // Feature register bit 0 = 1 requests the change persist across power
+    // cycles (non-volatile), rather than reverting at the next reset.
+    buffer[1] = 0x01;
+    encode_lba28(&mut buffer, native_max_lba);
+
+    let result = unsafe { libc::ioctl(fd, HDIO_DRIVE_CMD, buffer.as_mut_ptr()) };
+    if result != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
     Ok(())
 }
 
+/// Decode an LBA28 address from the task file registers returned by
+/// `HDIO_DRIVE_CMD`: `buffer[2]` = LBA 0-7, `buffer[3]` = LBA 8-15,
+/// `buffer[4]` = LBA 16-23, low nibble of `buffer[5]` = LBA 24-27.
+fn decode_lba28(buffer: &[u8; 7]) -> u64 {
+    buffer[2] as u64 | (buffer[3] as u64) << 8 | (buffer[4] as u64) << 16 | ((buffer[5] as u64) & 0x0f) << 24
+}
+
+/// Encode an LBA28 address into the task file registers for `HDIO_DRIVE_CMD`.
+fn encode_lba28(buffer: &mut [u8; 7], lba: u64) {
+    buffer[2] = (lba & 0xff) as u8;
+    buffer[3] = ((lba >> 8) & 0xff) as u8;
+    buffer[4] = ((lba >> 16) & 0xff) as u8;
+    buffer[5] = ((lba >> 24) & 0x0f) as u8 | 0xe0; // high nibble: LBA mode bits
+}
+
+/// Read the 256-word ATA IDENTIFY DEVICE page via `HDIO_GET_IDENTITY`.
+///
+/// Returns `None` if the device isn't an ATA disk that responds to the ioctl
+/// (e.g. NVMe, USB mass storage bridges that don't pass IDENTIFY through).
+pub(crate) fn read_ata_identify(fd: i32) -> Option<[u16; 256]> {
+    let mut words = [0u16; 256];
+    let result = unsafe { libc::ioctl(fd, HDIO_GET_IDENTITY, words.as_mut_ptr()) };
+    if result != 0 {
+        return None;
+    }
+    Some(words)
+}
+
+/// Probe the device for the hardware erase commands it supports: ATA
+/// SECURITY ERASE UNIT (plain and enhanced), NVMe Sanitize, and block
+/// discard/secure discard.
+fn probe_erase_support(fd: i32, context: &mut NwipeContext) {
+    let dev_name = Path::new(&context.device_name)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("");
+
+    if dev_name.starts_with("nvme") {
+        // Every NVMe device that implements the admin command set supports
+        // either Format NVM or the Sanitize command; treat Sanitize as
+        // available. If the controller rejects it, `erase::hardware_erase`
+        // returns the error; Format NVM is not tried.
+        context.supports_nvme_sanitize = true;
+    } else if let Some(words) = read_ata_identify(fd) {
+        // Word 128: security status. Bit 0 = security supported.
+        let security_status = words[128];
+        context.supports_ata_secure_erase = security_status & 0x0001 != 0;
+        // Bit 5 = enhanced erase supported.
+        context.supports_ata_enhanced_erase = context.supports_ata_secure_erase && security_status & 0x0020 != 0;
+    }
+
+    let queue_path = PathBuf::from(format!("/sys/block/{}/queue", dev_name));
+    let discard_max = fs::read_to_string(queue_path.join("discard_max_bytes"))
+        .ok()
+        .and_then(|s| s.trim().parse::<u64>().ok())
+        .unwrap_or(0);
+    context.supports_discard = discard_max > 0;
+
+    context.supports_secure_discard = fs::read_to_string(queue_path.join("discard_max_bytes"))
+        .ok()
+        .and_then(|_| fs::metadata(PathBuf::from(format!("/sys/block/{}/device/unmap_granularity", dev_name))).ok())
+        .is_some()
+        && context.supports_discard;
+
+    if context.supports_ata_secure_erase || context.supports_nvme_sanitize || context.supports_discard {
+        nwipe_log(
+            LogLevel::Info,
+            &format!(
+                "{} hardware erase support: ata_secure_erase={} ata_enhanced_erase={} nvme_sanitize={} discard={} secure_discard={}",
+                context.device_name,
+                context.supports_ata_secure_erase,
+                context.supports_ata_enhanced_erase,
+                context.supports_nvme_sanitize,
+                context.supports_discard,
+                context.supports_secure_discard
+            ),
+        );
+    }
+}
+
+/// Determine whether a device is rotational media, solid-state, or
+/// removable by reading the corresponding sysfs attributes.
+fn get_device_media_info(context: &mut NwipeContext) {
+    let dev_name = match Path::new(&context.device_name).file_name().and_then(|n| n.to_str()) {
+        Some(name) => name,
+        None => return,
+    };
+
+    let queue_path = PathBuf::from(format!("/sys/block/{}/queue", dev_name));
+
+    context.media_type = match fs::read_to_string(queue_path.join("rotational")) {
+        Ok(value) => match value.trim() {
+            "1" => MediaType::Rotational,
+            "0" => MediaType::SolidState,
+            _ => MediaType::Unknown,
+        },
+        Err(_) => MediaType::Unknown,
+    };
+
+    let removable_path = PathBuf::from(format!("/sys/block/{}/removable", dev_name));
+    context.is_removable = fs::read_to_string(removable_path)
+        .map(|value| value.trim() == "1")
+        .unwrap_or(false);
+
+    let media_str = match context.media_type {
+        MediaType::Rotational => "rotational (HDD)",
+        MediaType::SolidState => "solid-state (SSD/flash)",
+        MediaType::Unknown => "unknown",
+    };
+
+    nwipe_log(
+        LogLevel::Info,
+        &format!(
+            "{} media type: {}{}",
+            context.device_name,
+            media_str,
+            if context.is_removable { ", removable" } else { "" }
+        ),
+    );
+}
+
 /// Get device identity information.
+///
+/// Prefers data decoded from the ATA IDENTIFY DEVICE page over the sysfs
+/// `device/model`/`device/serial` strings, since the latter are frequently
+/// absent behind USB bridges. The `/dev/disk/by-id` WWN and stable path are
+/// resolved separately and always attached when available.
 fn get_device_identity(fd: i32, context: &mut NwipeContext) -> Result<(), io::Error> {
-    // In a real implementation, we would use ioctl calls to get device identity information
-    // For now, we'll just set some placeholder values
-
-    // Try to extract device model and serial from sysfs
     if let Some(device_info) = extract_device_info_from_sysfs(&context.device_name) {
         context.identity = device_info;
     } else {
-        // Set default values if sysfs info not available
         context.identity.model_no = "Unknown Model".to_string();
         context.identity.serial_no = "Unknown Serial".to_string();
         context.identity.firmware_rev = "Unknown Firmware".to_string();
     }
 
+    if let Some(words) = read_ata_identify(fd) {
+        let model = decode_ata_identify_string(&words[27..47]);
+        let serial = decode_ata_identify_string(&words[10..20]);
+        let firmware = decode_ata_identify_string(&words[23..27]);
+
+        if !model.is_empty() {
+            context.identity.model_no = model;
+        }
+        if !serial.is_empty() {
+            context.identity.serial_no = serial;
+        }
+        if !firmware.is_empty() {
+            context.identity.firmware_rev = firmware;
+        }
+    }
+
+    if let Some((by_id_path, wwn)) = resolve_by_id(&context.device_name) {
+        context.identity.by_id_path = by_id_path;
+        if let Some(wwn) = wwn {
+            context.identity.wwn = wwn;
+        }
+    }
+
     Ok(())
 }
 
+/// Decode an ASCII string packed into ATA IDENTIFY words.
+///
+/// Each word stores two characters with the first one in its high byte, so
+/// every word is emitted high byte first, then low byte.
+fn decode_ata_identify_string(words: &[u16]) -> String {
+    let mut bytes = Vec::with_capacity(words.len() * 2);
+    for word in words {
+        bytes.push((word >> 8) as u8);
+        bytes.push((word & 0xff) as u8);
+    }
+    String::from_utf8_lossy(&bytes).trim().to_string()
+}
+
+/// Resolve the stable `/dev/disk/by-id/*` path(s) for a device, returning the
+/// preferred by-id path and, if present, the `wwn-*` link's suffix as the WWN.
+fn resolve_by_id(device_name: &str) -> Option<(String, Option<String>)> {
+    let target = fs::canonicalize(device_name).ok()?;
+
+    let entries = fs::read_dir("/dev/disk/by-id").ok()?;
+
+    let mut by_id_path = None;
+    let mut wwn = None;
+
+    for entry in entries.flatten() {
+        let link_path = entry.path();
+        if fs::canonicalize(&link_path).ok().as_ref() != Some(&target) {
+            continue;
+        }
+
+        let name = link_path.file_name()?.to_str()?.to_string();
+
+        if name.starts_with("wwn-") {
+            wwn = Some(name.trim_start_matches("wwn-").to_string());
+        }
+
+        // Prefer a non-wwn link (e.g. ata-<model>_<serial>) as the canonical
+        // by-id path, falling back to the wwn link otherwise. When several
+        // non-wwn links exist, the last one listed wins.
+        if by_id_path.is_none() || !name.starts_with("wwn-") {
+            by_id_path = Some(link_path.to_string_lossy().to_string());
+        }
+    }
+
+    by_id_path.map(|path| (path, wwn))
+}
+
 /// Extract device information from sysfs.
 fn extract_device_info_from_sysfs(device_name: &str) -> Option<DeviceIdentity> {
     // Extract the device name without the /dev/ prefix
@@ -172,81 +495,134 @@ fn extract_device_info_from_sysfs(device_name: &str) -> Option<DeviceIdentity> {
 }
 
 /// Get device size.
-fn get_device_size(fd: i32, context: &mut NwipeContext) -> Result<(), io::Error> {
-    // In a real implementation, we would use ioctl calls to get device size
-    // For now, we'll use a placeholder implementation
-
-    // Try to get size using BLKGETSIZE64 ioctl
-    let mut size: u64 = 0;
-
-    // This is a placeholder for the actual ioctl call
-    // In real code, we would use something like:
-    // unsafe {
-    //     let result = libc::ioctl(fd, libc::BLKGETSIZE64, &mut size);
-    //     if result != 0 {
-    //         return Err(io::Error::last_os_error());
-    //     }
-    // }
-
-    // For now, just set a placeholder size
-    context.device_size = size;
-
-    // If we couldn't get the size, try to use lseek
-    if context.device_size == 0 {
-        // This is a placeholder for the actual lseek call
-        // In real code, we would use something like:
-        // let size = unsafe { libc::lseek64(fd, 0, libc::SEEK_END) };
-        // if size != -1 {
-        //     context.device_size = size as u64;
-        // }
+///
+/// Tries `BLKGETSIZE64` first (byte count), falls back to `BLKGETSIZE`
+/// (512-byte sector count) for older kernels, and finally falls back to
+/// `lseek(fd, 0, SEEK_END)` if both ioctls fail.
+pub(crate) fn get_device_size(fd: i32, context: &mut NwipeContext) -> Result<(), io::Error> {
+    let mut size64: u64 = 0;
+    let result = unsafe { libc::ioctl(fd, BLKGETSIZE64, &mut size64) };
+    if result == 0 {
+        context.device_size = size64;
+        return Ok(());
     }
 
-    // For demonstration purposes, set a reasonable size
-    if context.device_size == 0 {
-        context.device_size = 1024 * 1024 * 1024; // 1 GB
+    let mut sectors: libc::c_ulong = 0;
+    let result = unsafe { libc::ioctl(fd, BLKGETSIZE, &mut sectors) };
+    if result == 0 {
+        context.device_size = sectors as u64 * 512;
+        return Ok(());
     }
 
-    Ok(())
+    match lseek(fd, 0, Whence::SeekEnd) {
+        Ok(offset) => {
+            context.device_size = offset as u64;
+            Ok(())
+        }
+        Err(_) => Err(io::Error::last_os_error()),
+    }
 }
 
 /// Get device sector and block size.
-fn get_device_sector_block_size(fd: i32, context: &mut NwipeContext) -> Result<(), io::Error> {
-    // In a real implementation, we would use ioctl calls to get sector and block size
-    // For now, we'll use placeholder values
-
-    // Try to get sector size using BLKSSZGET ioctl
-    let mut sector_size: u64 = 0;
-
-    // This is a placeholder for the actual ioctl call
-    // In real code, we would use something like:
-    // unsafe {
-    //     let result = libc::ioctl(fd, libc::BLKSSZGET, &mut sector_size);
-    //     if result != 0 {
-    //         return Err(io::Error::last_os_error());
-    //     }
-    // }
-
-    // For now, just set a placeholder sector size
-    context.device_sector_size = 512;
-
-    // Try to get block size using BLKBSZGET ioctl
-    let mut block_size: i32 = 0;
-
-    // This is a placeholder for the actual ioctl call
-    // In real code, we would use something like:
-    // unsafe {
-    //     let result = libc::ioctl(fd, libc::BLKBSZGET, &mut block_size);
-    //     if result != 0 {
-    //         return Err(io::Error::last_os_error());
-    //     }
-    // }
-
-    // For now, just set a placeholder block size
-    context.device_block_size = 4096;
+///
+/// `BLKSSZGET` gives the logical sector size and `BLKPBSZGET` the
+/// physical/optimal I/O size, falling back to `BLKBSZGET` when the
+/// physical size isn't reported by the driver.
+pub(crate) fn get_device_sector_block_size(fd: i32, context: &mut NwipeContext) -> Result<(), io::Error> {
+    let mut sector_size: libc::c_int = 0;
+    let result = unsafe { libc::ioctl(fd, BLKSSZGET, &mut sector_size) };
+    if result != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    context.device_sector_size = sector_size as u64;
+
+    let mut physical_size: libc::c_int = 0;
+    let result = unsafe { libc::ioctl(fd, BLKPBSZGET, &mut physical_size) };
+    if result == 0 {
+        context.device_block_size = physical_size;
+        return Ok(());
+    }
+
+    let mut block_size: libc::c_int = 0;
+    let result = unsafe { libc::ioctl(fd, BLKBSZGET, &mut block_size) };
+    if result != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    context.device_block_size = block_size;
 
     Ok(())
 }
 
+/// Whether a wipe method name is an overwrite-based software method, as
+/// opposed to a hardware erase/discard method.
+pub fn is_overwrite_method(method: &str) -> bool {
+    matches!(method, "ops2" | "dod" | "gutmann" | "random" | "zero" | "encrypted-zero")
+}
+
+/// A block device being added or removed from the system.
+#[derive(Debug, Clone)]
+pub enum HotplugEvent {
+    /// A new block device node appeared.
+    Added(String),
+    /// A previously seen block device node disappeared.
+    Removed(String),
+}
+
+/// How often the hotplug monitor re-scans `/dev` for changes.
+const HOTPLUG_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Spawn a background thread that watches for block devices being attached
+/// or removed and reports them over the returned channel.
+///
+/// This polls `/sys/block` rather than opening a udev netlink socket, since
+/// that keeps the monitor dependency-free and works identically whether or
+/// not a udev daemon is running (e.g. in a minimal rescue environment).
+pub fn spawn_hotplug_monitor() -> Receiver<HotplugEvent> {
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        let mut known = list_block_devices();
+
+        loop {
+            thread::sleep(HOTPLUG_POLL_INTERVAL);
+
+            let current = list_block_devices();
+
+            for dev in current.difference(&known) {
+                if tx.send(HotplugEvent::Added(dev.clone())).is_err() {
+                    return;
+                }
+            }
+
+            for dev in known.difference(&current) {
+                if tx.send(HotplugEvent::Removed(dev.clone())).is_err() {
+                    return;
+                }
+            }
+
+            known = current;
+        }
+    });
+
+    rx
+}
+
+/// List the `/dev/<name>` paths of every block device currently registered
+/// under `/sys/block`.
+fn list_block_devices() -> HashSet<String> {
+    let mut devices = HashSet::new();
+
+    if let Ok(entries) = fs::read_dir("/sys/block") {
+        for entry in entries.flatten() {
+            if let Some(name) = entry.file_name().to_str() {
+                devices.insert(format!("/dev/{}", name));
+            }
+        }
+    }
+
+    devices
+}
+
 /// Check if a device is mounted.
 pub fn device_is_mounted(device_name: &str) -> bool {
     // Read /proc/mounts to check if the device is mounted
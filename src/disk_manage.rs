@@ -0,0 +1,179 @@
+/*
+ *  disk_manage.rs: Cached block-device topology for nwipe.
+ *
+ *  Copyright Sebastiaan Koetsier (2025)
+ *
+ *  This program is free software; you can redistribute it and/or modify it under
+ *  the terms of the GNU General Public License as published by the Free Software
+ *  Foundation, version 2.
+ */
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+
+/// The classification of a block device node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeKind {
+    /// A whole disk, safe to present for wiping.
+    WholeDisk,
+    /// A partition of a disk.
+    Partition,
+    /// A device that is held by another (LVM/MD/ZFS/dm member, etc.).
+    Member,
+}
+
+/// A cached view of the system's mounted block devices, built once per scan
+/// from `/proc/self/mountinfo` so that classifying many devices doesn't
+/// repeatedly re-read `/proc/mounts`. Sysfs topology is read per query.
+pub struct DiskManage {
+    /// The `(major, minor)` pairs of every currently mounted block device.
+    mounted: HashSet<(u64, u64)>,
+    /// The `(major, minor)` of the device backing the running root filesystem.
+    root_dev: Option<(u64, u64)>,
+}
+
+impl DiskManage {
+    /// Build the topology cache by parsing `/proc/self/mountinfo` once.
+    pub fn new() -> io::Result<Self> {
+        let mountinfo = fs::read_to_string("/proc/self/mountinfo")?;
+
+        let mut mounted = HashSet::new();
+        let mut root_dev = None;
+
+        for line in mountinfo.lines() {
+            // Format: id parent-id major:minor root mount-point ...
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 5 {
+                continue;
+            }
+
+            let dev_field = fields[2];
+            let (major, minor) = match parse_dev_field(dev_field) {
+                Some(pair) => pair,
+                None => continue,
+            };
+
+            mounted.insert((major, minor));
+
+            if fields[4] == "/" {
+                root_dev = Some((major, minor));
+            }
+        }
+
+        Ok(Self { mounted, root_dev })
+    }
+
+    /// Classify a whole-disk-candidate device node.
+    pub fn classify(&self, device_name: &str) -> io::Result<NodeKind> {
+        let dev_name = Path::new(device_name)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(device_name);
+
+        // Unlike /sys/block, which only ever has top-level entries for whole
+        // disks, /sys/class/block has one entry per block device node -
+        // partitions included - each carrying its own "partition" file, so
+        // this is the one place that can tell the two apart directly rather
+        // than having to locate the parent disk first.
+        let class_path = PathBuf::from(format!("/sys/class/block/{}", dev_name));
+
+        if !class_path.exists() {
+            // Not found under /sys/class/block at all (e.g. an unusual
+            // device node); treat conservatively as a member so it isn't
+            // silently wiped.
+            return Ok(NodeKind::Member);
+        }
+
+        if class_path.join("partition").exists() {
+            return Ok(NodeKind::Partition);
+        }
+
+        if self.has_holders(&class_path) {
+            return Ok(NodeKind::Member);
+        }
+
+        Ok(NodeKind::WholeDisk)
+    }
+
+    /// Whether any other block device (dm/md/zfs) holds this one open.
+    fn has_holders(&self, sys_path: &Path) -> bool {
+        match fs::read_dir(sys_path.join("holders")) {
+            Ok(mut entries) => entries.next().is_some(),
+            Err(_) => false,
+        }
+    }
+
+    /// Whether the device identified by `device_name` is currently mounted.
+    /// Mounts are recorded in `/proc/self/mountinfo` against the *partition*
+    /// device node, not the whole disk, so this checks the disk's own
+    /// major:minor together with every one of its partitions'.
+    pub fn is_mounted(&self, device_name: &str) -> bool {
+        self.related_devs(device_name).iter().any(|dev| self.mounted.contains(dev))
+    }
+
+    /// Whether the device identified by `device_name` holds the running
+    /// root filesystem and should be protected from accidental wiping. Like
+    /// `is_mounted`, this checks the disk and all of its partitions, since
+    /// root is mounted from a partition (e.g. `/dev/sda1`), not the whole
+    /// disk (`/dev/sda`).
+    pub fn is_protected_root(&self, device_name: &str) -> bool {
+        let root_dev = match self.root_dev {
+            Some(dev) => dev,
+            None => return false,
+        };
+
+        self.related_devs(device_name).iter().any(|&dev| dev == root_dev)
+    }
+
+    /// Collect the `(major, minor)` of `device_name` itself plus every one
+    /// of its partitions, by reading `/sys/block/<dev>/<partN>/dev`. Needed
+    /// because mountinfo and the root lookup above key off a partition's own
+    /// device node, never the whole disk's.
+    fn related_devs(&self, device_name: &str) -> Vec<(u64, u64)> {
+        let mut devs = Vec::new();
+
+        if let Ok(meta) = fs::metadata(device_name) {
+            devs.push(rdev_to_pair(meta.rdev()));
+        }
+
+        let dev_name = Path::new(device_name)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(device_name);
+
+        let sys_path = PathBuf::from(format!("/sys/block/{}", dev_name));
+        if let Ok(entries) = fs::read_dir(&sys_path) {
+            for entry in entries.flatten() {
+                let part_path = entry.path();
+                if !part_path.join("partition").exists() {
+                    continue;
+                }
+                if let Ok(dev_field) = fs::read_to_string(part_path.join("dev")) {
+                    if let Some(pair) = parse_dev_field(dev_field.trim()) {
+                        devs.push(pair);
+                    }
+                }
+            }
+        }
+
+        devs
+    }
+}
+
+/// Split a `st_rdev` value into its `(major, minor)` components.
+fn rdev_to_pair(rdev: u64) -> (u64, u64) {
+    let major = (rdev >> 8) & 0xfff;
+    let minor = (rdev & 0xff) | ((rdev >> 12) & 0xfff00);
+    (major, minor)
+}
+
+/// Parse a `major:minor` mountinfo field.
+fn parse_dev_field(field: &str) -> Option<(u64, u64)> {
+    let mut parts = field.split(':');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    Some((major, minor))
+}
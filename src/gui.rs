@@ -22,6 +22,7 @@ use crossterm::{
     terminal::{self, ClearType},
 };
 
+use crate::cancel::CancelFlag;
 use crate::context::{NwipeContext, SelectStatus, PassType};
 use crate::logging::convert_seconds_to_hours_minutes_seconds;
 
@@ -133,7 +134,7 @@ pub fn gui_options() {
 }
 
 /// Display the device selection screen.
-pub fn gui_select(count: usize, contexts: &mut Vec<NwipeContext>) {
+pub fn gui_select(count: usize, contexts: &mut Vec<NwipeContext>, user_abort: &CancelFlag) {
     let (width, height) = terminal::size().unwrap();
 
     // Current selection
@@ -221,7 +222,7 @@ pub fn gui_select(count: usize, contexts: &mut Vec<NwipeContext>) {
                     },
                     KeyCode::Char('q') | KeyCode::Char('Q') => {
                         // Quit
-                        unsafe { crate::USER_ABORT = true; }
+                        user_abort.set();
                         break;
                     },
                     _ => {},
@@ -232,7 +233,7 @@ pub fn gui_select(count: usize, contexts: &mut Vec<NwipeContext>) {
 }
 
 /// Display the status screen.
-pub fn gui_status(contexts: &[NwipeContext], _count: usize) {
+pub fn gui_status(contexts: &[NwipeContext], _count: usize, terminate: &CancelFlag) {
     let (width, height) = terminal::size().unwrap();
 
     // Status update interval - increased to reduce flickering
@@ -288,7 +289,7 @@ pub fn gui_status(contexts: &[NwipeContext], _count: usize) {
     // Main loop
     loop {
         // Check if we should exit
-        if unsafe { crate::TERMINATE_SIGNAL } {
+        if terminate.is_set() {
             break;
         }
 
@@ -379,10 +380,7 @@ pub fn gui_status(contexts: &[NwipeContext], _count: usize) {
                 match key.code {
                     KeyCode::Char('q') | KeyCode::Char('Q') => {
                         // Quit
-                        unsafe {
-                            crate::TERMINATE_SIGNAL = true;
-                            crate::USER_ABORT = true;
-                        }
+                        terminate.set();
                         break;
                     },
                     _ => {},
@@ -8,6 +8,9 @@
  *  Foundation, version 2.
  */
 
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver};
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::{Duration, Instant};
@@ -16,14 +19,64 @@ use eframe::{egui, CreationContext};
 use egui::{Color32, RichText, Ui};
 use egui_extras::{Size, StripBuilder, TableBuilder};
 use rfd::FileDialog;
+use serde::{Deserialize, Serialize};
 
+use crate::cancel;
 use crate::context::{NwipeContext, PassType, SelectStatus};
-use crate::device;
-use crate::logging::{self, LogLevel, nwipe_log};
+use crate::device::{self, HotplugEvent};
+use crate::file_erase::{self, FileEraseOptions, FileEraseProgress};
+use crate::i18n::{self, Locale};
+use crate::journal;
+use crate::logging::{self, LogLevel, LogRecord, nwipe_log};
 use crate::method;
 use crate::options::NwipeOptions;
+use crate::update_check::{self, UpdateStatus};
 use crate::version;
 
+/// The key the config round-trips under in eframe's storage backend.
+const CONFIG_KEY: &str = "nwipe-config";
+
+/// How long the "Begin Wipe" button stays disabled after the confirmation
+/// dialog opens, giving the user a forced pause before an irreversible action.
+const CONFIRM_COUNTDOWN_SECS: u64 = 10;
+
+/// The subset of `NwipeApp` state persisted across restarts via
+/// `eframe::App::save`/`cc.storage`: last-used method/PRNG/options and the
+/// window size.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct NwipeAppConfig {
+    method: String,
+    prng: String,
+    rounds: i32,
+    verify: bool,
+    autopoweroff: bool,
+    window_width: f32,
+    window_height: f32,
+    locale: Locale,
+}
+
+impl Default for NwipeAppConfig {
+    fn default() -> Self {
+        Self {
+            method: "ops2".to_string(),
+            prng: "isaac".to_string(),
+            rounds: 1,
+            verify: true,
+            autopoweroff: false,
+            window_width: 1024.0,
+            window_height: 768.0,
+            locale: Locale::default(),
+        }
+    }
+}
+
+/// What a wipe run targets: a whole block device, or a single file/directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TargetMode {
+    Disk,
+    FileOrDirectory,
+}
+
 /// The main GUI application.
 pub struct NwipeApp {
     /// The list of available devices.
@@ -38,12 +91,13 @@ pub struct NwipeApp {
     verify: bool,
     /// Whether wiping is in progress.
     wiping_in_progress: bool,
+    /// Cancellation flag shared with every running wipe thread; a fresh one
+    /// is created each time `start_wiping` runs.
+    cancel: cancel::CancelFlag,
     /// The wiping threads.
     wipe_threads: Vec<thread::JoinHandle<()>>,
     /// The last update time.
     last_update: Instant,
-    /// Log messages.
-    log_messages: Arc<Mutex<Vec<String>>>,
     /// Whether to show the about dialog.
     show_about: bool,
     /// Whether to show the help dialog.
@@ -54,13 +108,55 @@ pub struct NwipeApp {
     show_settings: bool,
     /// Whether to power off after wiping.
     autopoweroff: bool,
+    /// Receiver for hotplug add/remove events from the background monitor.
+    hotplug_rx: Receiver<HotplugEvent>,
+    /// Device names currently undergoing a wipe, protected from hotplug removal.
+    wiping_devices: HashSet<String>,
+    /// Receiver for `ProgressUpdate`s from the currently running wipe threads.
+    progress_rx: Option<Receiver<method::ProgressUpdate>>,
+    /// The most recent progress report received for each device being wiped.
+    progress: HashMap<String, method::ProgressUpdate>,
+    /// Shared ring buffer of every `nwipe_log` record, populated by the
+    /// logging module and rendered in the log pane below.
+    log_ring: Arc<Mutex<VecDeque<LogRecord>>>,
+    /// Minimum severity a record must have (in `LogLevel` order) to be shown.
+    log_level_filter: LogLevel,
+    /// Confirmation phrase the user must type before the wipe can start.
+    confirm_phrase: String,
+    /// The text currently typed into the confirmation field.
+    confirm_input: String,
+    /// Acknowledgement checkbox: the user confirms the data is unrecoverable.
+    confirm_ack_unrecoverable: bool,
+    /// Acknowledgement checkbox: the user confirms the selected disks are correct.
+    confirm_ack_correct_disks: bool,
+    /// The "Begin Wipe" button is disabled until this instant elapses,
+    /// forcing a pause before the confirmation can be completed.
+    confirm_countdown_until: Option<Instant>,
+    /// Receiver for the background update check's result, if one is running.
+    update_check_rx: Option<Receiver<UpdateStatus>>,
+    /// The most recent update check result, shown in the "Update Check" window.
+    update_status: Option<UpdateStatus>,
+    /// The current window size, tracked each frame so `save()` can persist it.
+    window_size: egui::Vec2,
+    /// Whether the next wipe targets a whole disk or a file/directory.
+    target_mode: TargetMode,
+    /// The file or directory chosen for a file-mode erase.
+    file_target_path: Option<PathBuf>,
+    /// Whether a directory target is erased recursively.
+    file_target_recursive: bool,
+    /// Receiver for per-file progress from a running file-mode erase.
+    file_progress_rx: Option<Receiver<FileEraseProgress>>,
+    /// The most recent per-file progress report for a running file-mode erase.
+    file_progress: Option<FileEraseProgress>,
+    /// The active UI locale, also applied to `i18n::t()` lookups.
+    locale: Locale,
 }
 
 impl Default for NwipeApp {
     fn default() -> Self {
         // Scan for devices
         let mut devices = Vec::new();
-        if let Ok(count) = device::device_scan(&mut devices) {
+        if let Ok(count) = device::device_scan(&mut devices, false) {
             nwipe_log(LogLevel::Info, &format!("Found {} devices", count));
         } else {
             nwipe_log(LogLevel::Error, "Failed to scan for devices");
@@ -73,14 +169,34 @@ impl Default for NwipeApp {
             rounds: 1,
             verify: true,
             wiping_in_progress: false,
+            cancel: cancel::CancelFlag::new(),
             wipe_threads: Vec::new(),
             last_update: Instant::now(),
-            log_messages: Arc::new(Mutex::new(Vec::new())),
             show_about: false,
             show_help: false,
             show_confirmation: false,
             show_settings: false,
             autopoweroff: false,
+            hotplug_rx: device::spawn_hotplug_monitor(),
+            wiping_devices: HashSet::new(),
+            progress_rx: None,
+            progress: HashMap::new(),
+            log_ring: logging::log_ring(),
+            log_level_filter: LogLevel::Debug,
+            confirm_phrase: String::new(),
+            confirm_input: String::new(),
+            confirm_ack_unrecoverable: false,
+            confirm_ack_correct_disks: false,
+            confirm_countdown_until: None,
+            update_check_rx: None,
+            update_status: None,
+            window_size: egui::vec2(1024.0, 768.0),
+            target_mode: TargetMode::Disk,
+            file_target_path: None,
+            file_target_recursive: true,
+            file_progress_rx: None,
+            file_progress: None,
+            locale: Locale::default(),
         }
     }
 }
@@ -105,14 +221,83 @@ impl NwipeApp {
         style.visuals.widgets.hovered.bg_fill = Color32::from_rgb(80, 80, 80);
         cc.egui_ctx.set_style(style);
 
-        Self::default()
+        // Load persisted settings, if eframe has a storage backend available.
+        let config = cc
+            .storage
+            .and_then(|storage| eframe::get_value::<NwipeAppConfig>(storage, CONFIG_KEY))
+            .unwrap_or_default();
+
+        cc.egui_ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(egui::vec2(
+            config.window_width,
+            config.window_height,
+        )));
+
+        i18n::set_locale(config.locale);
+
+        Self {
+            method: config.method,
+            prng: config.prng,
+            rounds: config.rounds,
+            verify: config.verify,
+            autopoweroff: config.autopoweroff,
+            window_size: egui::vec2(config.window_width, config.window_height),
+            locale: config.locale,
+            ..Self::default()
+        }
+    }
+
+    /// Drain pending hotplug events and merge them into the device list
+    /// incrementally, preserving selection/progress state of existing
+    /// entries and never touching a device that is currently being wiped.
+    fn handle_hotplug_events(&mut self) {
+        let events: Vec<HotplugEvent> = self.hotplug_rx.try_iter().collect();
+        if events.is_empty() {
+            return;
+        }
+
+        let mut devices = self.devices.lock().unwrap();
+
+        for event in events {
+            match event {
+                HotplugEvent::Added(path) => {
+                    if devices.iter().any(|d| d.device_name == path) {
+                        continue;
+                    }
+
+                    let mut scanned = Vec::new();
+                    match device::device_get(&mut scanned, &[path.clone()]) {
+                        Ok(1) => {
+                            nwipe_log(LogLevel::Notice, &format!("Device attached: {}", path));
+                            devices.push(scanned.remove(0));
+                        }
+                        _ => {
+                            nwipe_log(LogLevel::Warning, &format!("Device attached but could not be scanned: {}", path));
+                        }
+                    }
+                }
+                HotplugEvent::Removed(path) => {
+                    if self.wiping_devices.contains(&path) {
+                        nwipe_log(
+                            LogLevel::Error,
+                            &format!("Device {} disappeared while a wipe was in progress!", path),
+                        );
+                        continue;
+                    }
+
+                    if devices.iter().any(|d| d.device_name == path) {
+                        nwipe_log(LogLevel::Notice, &format!("Device removed: {}", path));
+                        devices.retain(|d| d.device_name != path);
+                    }
+                }
+            }
+        }
     }
 
     /// Refresh the device list.
     fn refresh_devices(&mut self) {
         let mut devices = self.devices.lock().unwrap();
         devices.clear();
-        if let Ok(count) = device::device_scan(&mut *devices) {
+        if let Ok(count) = device::device_scan(&mut *devices, false) {
             nwipe_log(LogLevel::Info, &format!("Found {} devices", count));
         } else {
             nwipe_log(LogLevel::Error, "Failed to scan for devices");
@@ -140,21 +325,36 @@ impl NwipeApp {
 
         // Clone the devices for the wiping threads
         let devices_arc = Arc::clone(&self.devices);
-        let log_messages = Arc::clone(&self.log_messages);
+
+        // Track which devices are actively being wiped so the hotplug
+        // monitor never drops or mutates their entry mid-wipe.
+        self.wiping_devices = selected_devices.iter().map(|d| d.device_name.clone()).collect();
+        self.progress.clear();
+
+        // All wipe threads share one channel; each ProgressUpdate carries its
+        // own device_name so the receiver can tell them apart.
+        let (progress_tx, progress_rx) = mpsc::channel::<method::ProgressUpdate>();
+        self.progress_rx = Some(progress_rx);
+
+        // A fresh cancellation flag for this run, cloned into every wipe
+        // thread; `stop_wiping` sets it to request a clean unwind.
+        self.cancel = cancel::CancelFlag::new();
 
         // Start wiping threads
         for device in selected_devices {
             let devices_arc = Arc::clone(&devices_arc);
-            let log_messages = Arc::clone(&log_messages);
             let method = self.method.clone();
             let prng = self.prng.clone();
             let rounds = self.rounds;
             let verify = self.verify;
+            let progress_tx = Some(progress_tx.clone());
+            let cancel = self.cancel.clone();
 
             let handle = thread::spawn(move || {
                 // Set up the context for wiping
                 let mut context = device.clone();
                 context.prng = prng;
+                context.wipe_method = method.clone();
                 context.round_count = rounds;
                 context.verify = verify;
 
@@ -164,13 +364,27 @@ impl NwipeApp {
                     &format!("Starting wipe of device {}", context.device_name),
                 );
 
+                // Pick up a progress journal left behind by a prior interrupted
+                // run of this same method on this device, if any, so the wipe
+                // resumes instead of restarting the device from scratch.
+                // Methods without a way to resume safely (see
+                // `method::resume_supported`) always restart instead.
+                let journal_entry = if method::resume_supported(&method) {
+                    journal::load(&context.device_name, &context.wipe_method)
+                } else {
+                    None
+                };
+                let mut resume = method::Resume::new(journal_entry);
+
                 // Perform the wipe
                 let result = match method.as_str() {
-                    "ops2" => method::ops2_wipe(&mut context),
-                    "dod" => method::dod_wipe(&mut context),
-                    "gutmann" => method::gutmann_wipe(&mut context),
-                    "random" => method::random_wipe(&mut context),
-                    "zero" => method::zero_wipe(&mut context),
+                    "ops2" => method::ops2_wipe(&mut context, &progress_tx, &cancel, &mut resume),
+                    "dod" => method::dod_wipe(&mut context, &progress_tx, &cancel, &mut resume),
+                    "gutmann" => method::gutmann_wipe(&mut context, &progress_tx, &cancel, &mut resume),
+                    "random" => method::random_wipe(&mut context, &progress_tx, &cancel, &mut resume),
+                    "zero" => method::zero_wipe(&mut context, &progress_tx, &cancel, &mut resume),
+                    "encrypted-zero" => method::encrypted_zero_wipe(&mut context, &progress_tx, &cancel, &mut resume),
+                    "secure-erase" => method::secure_erase_wipe(&mut context, &progress_tx, &cancel, &mut resume),
                     _ => {
                         nwipe_log(
                             LogLevel::Error,
@@ -180,6 +394,11 @@ impl NwipeApp {
                     }
                 };
 
+                // A successful completion leaves nothing to resume.
+                if result == 0 {
+                    journal::clear(&context.device_name);
+                }
+
                 // Update the device status
                 let mut devices = devices_arc.lock().unwrap();
                 for d in devices.iter_mut() {
@@ -214,30 +433,104 @@ impl NwipeApp {
         self.wiping_in_progress = true;
     }
 
+    /// Start a secure erase of the selected file or directory.
+    fn start_file_erase(&mut self) {
+        if self.wiping_in_progress {
+            return;
+        }
+
+        let Some(path) = self.file_target_path.clone() else {
+            nwipe_log(LogLevel::Warning, "No file or directory selected for erasure");
+            return;
+        };
+
+        self.file_progress = None;
+        let (progress_tx, progress_rx) = mpsc::channel::<FileEraseProgress>();
+        self.file_progress_rx = Some(progress_rx);
+
+        let options = FileEraseOptions {
+            method: self.method.clone(),
+            prng: self.prng.clone(),
+            recursive: self.file_target_recursive,
+        };
+        let progress_tx = Some(progress_tx);
+
+        // A fresh cancellation flag for this run, matching the disk-wipe
+        // path; `stop_wiping` cancels whichever kind of erase is running.
+        self.cancel = cancel::CancelFlag::new();
+        let cancel = self.cancel.clone();
+
+        nwipe_log(LogLevel::Notice, &format!("Starting secure erase of {}", path.display()));
+
+        let handle = thread::spawn(move || {
+            let summary = file_erase::secure_erase_path(&path, &options, &progress_tx, &cancel);
+
+            for error in &summary.errors {
+                nwipe_log(LogLevel::Error, &format!("File erase error: {}", error));
+            }
+
+            nwipe_log(
+                LogLevel::Notice,
+                &format!(
+                    "Secure erase of {} completed: {} file(s), {} bytes processed",
+                    path.display(),
+                    summary.files_processed,
+                    summary.bytes_processed
+                ),
+            );
+        });
+
+        self.wipe_threads.push(handle);
+        self.wiping_in_progress = true;
+    }
+
     /// Stop wiping.
     fn stop_wiping(&mut self) {
         if !self.wiping_in_progress {
             return;
         }
 
-        // Set the termination flag
-        unsafe {
-            crate::TERMINATE_SIGNAL = true;
-            crate::USER_ABORT = true;
-        }
+        // Request cancellation; every wipe thread holds a clone of
+        // `self.cancel` and polls it between write chunks.
+        self.cancel.set();
 
         // Wait for the wiping threads to finish
         for handle in self.wipe_threads.drain(..) {
             let _ = handle.join();
         }
 
-        // Reset the termination flag
-        unsafe {
-            crate::TERMINATE_SIGNAL = false;
-            crate::USER_ABORT = false;
+        self.wiping_in_progress = false;
+        self.wiping_devices.clear();
+        self.progress.clear();
+        self.progress_rx = None;
+        self.file_progress_rx = None;
+    }
+
+    /// Drain any pending progress reports from the wipe threads, keeping
+    /// only the most recent update per device.
+    fn drain_progress_updates(&mut self) {
+        let Some(rx) = &self.progress_rx else { return };
+        let updates: Vec<method::ProgressUpdate> = rx.try_iter().collect();
+        for update in updates {
+            self.progress.insert(update.device_name.clone(), update);
+        }
+    }
+
+    /// Pick up any pending per-file progress reports from a running file-mode erase.
+    fn drain_file_progress(&mut self) {
+        let Some(rx) = &self.file_progress_rx else { return };
+        for update in rx.try_iter() {
+            self.file_progress = Some(update);
         }
+    }
 
-        self.wiping_in_progress = false;
+    /// Pick up the background update check's result, if it has finished.
+    fn drain_update_check(&mut self) {
+        let Some(rx) = &self.update_check_rx else { return };
+        if let Ok(status) = rx.try_recv() {
+            self.update_status = Some(status);
+            self.update_check_rx = None;
+        }
     }
 
     /// Check if all wiping threads have finished.
@@ -262,6 +555,10 @@ impl NwipeApp {
             }
 
             self.wiping_in_progress = false;
+            self.wiping_devices.clear();
+            self.progress.clear();
+            self.progress_rx = None;
+            self.file_progress_rx = None;
 
             // Log the completion of all wiping
             nwipe_log(LogLevel::Notice, "All wiping operations completed");
@@ -279,6 +576,21 @@ impl NwipeApp {
 
 impl eframe::App for NwipeApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // Merge any hotplug add/remove events since the last frame
+        self.handle_hotplug_events();
+
+        // Pick up any live progress reports from the wipe threads
+        self.drain_progress_updates();
+        self.drain_file_progress();
+
+        // Pick up the background update check's result, if it finished
+        self.drain_update_check();
+
+        // Track the current window size so save() can persist it
+        if let Some(rect) = ctx.input(|i| i.viewport().inner_rect) {
+            self.window_size = rect.size();
+        }
+
         // Check if wiping has finished
         self.check_wiping_finished();
 
@@ -291,29 +603,35 @@ impl eframe::App for NwipeApp {
         // Top panel with menu
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
             egui::menu::bar(ui, |ui| {
-                ui.menu_button("File", |ui| {
-                    if ui.button("Refresh Devices").clicked() {
+                ui.menu_button(i18n::t("menu.file"), |ui| {
+                    if ui.button(i18n::t("menu.refresh_devices")).clicked() {
                         self.refresh_devices();
                         ui.close_menu();
                     }
-                    if ui.button("Settings").clicked() {
+                    if ui.button(i18n::t("menu.settings")).clicked() {
                         self.show_settings = true;
                         ui.close_menu();
                     }
                     ui.separator();
-                    if ui.button("Exit").clicked() {
+                    if ui.button(i18n::t("menu.exit")).clicked() {
                         ctx.send_viewport_cmd(egui::ViewportCommand::Close);
                     }
                 });
-                ui.menu_button("Help", |ui| {
-                    if ui.button("Help").clicked() {
+                ui.menu_button(i18n::t("menu.help"), |ui| {
+                    if ui.button(i18n::t("menu.help")).clicked() {
                         self.show_help = true;
                         ui.close_menu();
                     }
-                    if ui.button("About").clicked() {
+                    if ui.button(i18n::t("menu.about")).clicked() {
                         self.show_about = true;
                         ui.close_menu();
                     }
+                    ui.separator();
+                    if ui.button(i18n::t("menu.check_updates")).clicked() {
+                        self.update_status = Some(UpdateStatus::Checking);
+                        self.update_check_rx = Some(update_check::spawn_update_check());
+                        ui.close_menu();
+                    }
                 });
             });
         });
@@ -400,6 +718,39 @@ impl eframe::App for NwipeApp {
                 ui.heading("Wipe Options");
                 ui.separator();
 
+                ui.horizontal(|ui| {
+                    ui.label("Target:");
+                    ui.selectable_value(&mut self.target_mode, TargetMode::Disk, "Whole disk");
+                    ui.selectable_value(&mut self.target_mode, TargetMode::FileOrDirectory, "File/Directory");
+                });
+
+                if self.target_mode == TargetMode::FileOrDirectory {
+                    ui.horizontal(|ui| {
+                        if ui.button("Choose File...").clicked() {
+                            if let Some(path) = FileDialog::new().pick_file() {
+                                self.file_target_path = Some(path);
+                            }
+                        }
+                        if ui.button("Choose Folder...").clicked() {
+                            if let Some(path) = FileDialog::new().pick_folder() {
+                                self.file_target_path = Some(path);
+                            }
+                        }
+                    });
+
+                    match &self.file_target_path {
+                        Some(path) => {
+                            ui.label(format!("Target: {}", path.display()));
+                        }
+                        None => {
+                            ui.label(RichText::new("No file or directory selected").color(Color32::GRAY));
+                        }
+                    }
+
+                    ui.checkbox(&mut self.file_target_recursive, "Recurse into subdirectories");
+                    ui.separator();
+                }
+
                 ui.horizontal(|ui| {
                     ui.label("Method:");
                     egui::ComboBox::from_id_source("method_combo")
@@ -410,6 +761,8 @@ impl eframe::App for NwipeApp {
                             ui.selectable_value(&mut self.method, "gutmann".to_string(), "Gutmann (35 passes)");
                             ui.selectable_value(&mut self.method, "random".to_string(), "Random");
                             ui.selectable_value(&mut self.method, "zero".to_string(), "Zero");
+                            ui.selectable_value(&mut self.method, "encrypted-zero".to_string(), "Encrypted Zero (pseudo-random ciphertext)");
+                            ui.selectable_value(&mut self.method, "secure-erase".to_string(), "Hardware Secure Erase (SSD/NVMe)");
                         });
                 });
 
@@ -420,6 +773,7 @@ impl eframe::App for NwipeApp {
                         .show_ui(ui, |ui| {
                             ui.selectable_value(&mut self.prng, "isaac".to_string(), "ISAAC");
                             ui.selectable_value(&mut self.prng, "mt19937".to_string(), "MT19937");
+                            ui.selectable_value(&mut self.prng, "chacha".to_string(), "ChaCha20");
                             ui.selectable_value(&mut self.prng, "random".to_string(), "System Random");
                         });
                 });
@@ -436,18 +790,48 @@ impl eframe::App for NwipeApp {
 
                 ui.horizontal(|ui| {
                     if !self.wiping_in_progress {
-                        if ui.button("Start Wiping").clicked() {
-                            // Check if any devices are selected
-                            let devices = self.devices.lock().unwrap();
-                            let selected_count = devices.iter().filter(|d| d.select == SelectStatus::True).count();
-                            if selected_count > 0 {
-                                self.show_confirmation = true;
-                            } else {
-                                nwipe_log(LogLevel::Warning, "No devices selected for wiping");
+                        if ui.button(i18n::t("action.start_wiping")).clicked() {
+                            match self.target_mode {
+                                TargetMode::Disk => {
+                                    // Check if any devices are selected
+                                    let devices = self.devices.lock().unwrap();
+                                    let selected: Vec<&NwipeContext> =
+                                        devices.iter().filter(|d| d.select == SelectStatus::True).collect();
+                                    if !selected.is_empty() {
+                                        // A single target must be confirmed by its exact device
+                                        // node name; multiple targets fall back to a count phrase.
+                                        self.confirm_phrase = if let [only] = selected.as_slice() {
+                                            only.device_name.clone()
+                                        } else {
+                                            format!("{} ERASE", selected.len())
+                                        };
+                                        self.confirm_input.clear();
+                                        self.confirm_ack_unrecoverable = false;
+                                        self.confirm_ack_correct_disks = false;
+                                        self.confirm_countdown_until =
+                                            Some(Instant::now() + Duration::from_secs(CONFIRM_COUNTDOWN_SECS));
+                                        self.show_confirmation = true;
+                                    } else {
+                                        nwipe_log(LogLevel::Warning, "No devices selected for wiping");
+                                    }
+                                }
+                                TargetMode::FileOrDirectory => {
+                                    if let Some(path) = self.file_target_path.clone() {
+                                        self.confirm_phrase = path.display().to_string();
+                                        self.confirm_input.clear();
+                                        self.confirm_ack_unrecoverable = false;
+                                        self.confirm_ack_correct_disks = false;
+                                        self.confirm_countdown_until =
+                                            Some(Instant::now() + Duration::from_secs(CONFIRM_COUNTDOWN_SECS));
+                                        self.show_confirmation = true;
+                                    } else {
+                                        nwipe_log(LogLevel::Warning, "No file or directory selected for erasure");
+                                    }
+                                }
                             }
                         }
                     } else {
-                        if ui.button("Stop Wiping").clicked() {
+                        if ui.button(i18n::t("action.stop_wiping")).clicked() {
                             self.stop_wiping();
                         }
                     }
@@ -466,10 +850,27 @@ impl eframe::App for NwipeApp {
                         ui.heading("Wipe Progress");
                         ui.separator();
 
+                        if self.target_mode == TargetMode::FileOrDirectory {
+                            match &self.file_progress {
+                                Some(progress) => {
+                                    ui.label(format!("Current file: {}", progress.path.display()));
+                                    ui.label(format!(
+                                        "{} file(s) processed, {} bytes processed",
+                                        progress.files_processed, progress.bytes_processed
+                                    ));
+                                }
+                                None => {
+                                    ui.label("No file erase in progress.");
+                                }
+                            }
+                            return;
+                        }
+
                         // Create a table for the progress
                         TableBuilder::new(ui)
                             .column(egui_extras::Column::auto().at_least(100.0)) // Device name
                             .column(egui_extras::Column::remainder()) // Progress bar
+                            .column(egui_extras::Column::exact(140.0)) // Throughput / ETA
                             .column(egui_extras::Column::exact(100.0)) // Status
                             .header(20.0, |mut header| {
                                 header.col(|ui| {
@@ -478,6 +879,9 @@ impl eframe::App for NwipeApp {
                                 header.col(|ui| {
                                     ui.heading("Progress");
                                 });
+                                header.col(|ui| {
+                                    ui.heading("Throughput");
+                                });
                                 header.col(|ui| {
                                     ui.heading("Status");
                                 });
@@ -486,13 +890,31 @@ impl eframe::App for NwipeApp {
                                 let devices = self.devices.lock().unwrap();
                                 for device in devices.iter() {
                                     if device.select == SelectStatus::True {
+                                        let live = self.progress.get(&device.device_name);
                                         body.row(30.0, |mut row| {
                                             row.col(|ui| {
                                                 ui.label(&device.device_name);
                                             });
                                             row.col(|ui| {
-                                                let progress = device.round_percent / 100.0;
-                                                ui.add(egui::ProgressBar::new(progress as f32).show_percentage());
+                                                let progress = match live {
+                                                    Some(update) => update.percent / 100.0,
+                                                    None => device.round_percent / 100.0,
+                                                };
+                                                let bar = egui::ProgressBar::new(progress as f32).show_percentage();
+                                                match live {
+                                                    Some(update) => ui.add(bar.text(update.pass_label.clone())),
+                                                    None => ui.add(bar),
+                                                };
+                                            });
+                                            row.col(|ui| {
+                                                match live {
+                                                    Some(update) if update.throughput_bps > 0 => {
+                                                        ui.label(format!("{} MB/s", update.throughput_bps / 1_000_000));
+                                                    }
+                                                    _ => {
+                                                        ui.label("-");
+                                                    }
+                                                }
                                             });
                                             row.col(|ui| {
                                                 let status = match device.wipe_status {
@@ -529,22 +951,52 @@ impl eframe::App for NwipeApp {
 
                     // Log area
                     strip.cell(|ui| {
-                        ui.heading("Log");
+                        ui.horizontal(|ui| {
+                            ui.heading("Log");
+                            ui.separator();
+
+                            ui.label("Min level:");
+                            egui::ComboBox::from_id_source("log_level_combo")
+                                .selected_text(self.log_level_filter.to_string())
+                                .show_ui(ui, |ui| {
+                                    for level in [
+                                        LogLevel::Fatal,
+                                        LogLevel::Error,
+                                        LogLevel::Warning,
+                                        LogLevel::Notice,
+                                        LogLevel::Info,
+                                        LogLevel::Debug,
+                                    ] {
+                                        ui.selectable_value(&mut self.log_level_filter, level, level.to_string());
+                                    }
+                                });
+
+                            if ui.button("Copy all").clicked() {
+                                let ring = self.log_ring.lock().unwrap();
+                                let text = ring
+                                    .iter()
+                                    .map(|record| format!("{} {} {}", record.timestamp, record.level, record.message))
+                                    .collect::<Vec<_>>()
+                                    .join("\n");
+                                ui.output_mut(|o| o.copied_text = text);
+                            }
+                        });
                         ui.separator();
 
                         egui::ScrollArea::vertical().stick_to_bottom(true).show(ui, |ui| {
-                            // Get the log messages
-                            let log_messages = self.log_messages.lock().unwrap();
-
-                            // Display the log messages
-                            for message in log_messages.iter() {
-                                ui.label(message);
+                            let ring = self.log_ring.lock().unwrap();
+                            for record in ring.iter().filter(|record| record.level <= self.log_level_filter) {
+                                let color = match record.level {
+                                    LogLevel::Fatal | LogLevel::Error => Color32::RED,
+                                    LogLevel::Warning => Color32::YELLOW,
+                                    LogLevel::Notice => Color32::GREEN,
+                                    LogLevel::Info | LogLevel::Debug => Color32::GRAY,
+                                };
+                                ui.label(RichText::new(format!(
+                                    "{} {} {}",
+                                    record.timestamp, record.level, record.message
+                                )).color(color));
                             }
-
-                            // Also display the global log messages
-                            // This is a simplified approach; in a real implementation,
-                            // you would need to capture log messages from the logging system
-                            // and display them here.
                         });
                     });
                 });
@@ -552,24 +1004,81 @@ impl eframe::App for NwipeApp {
 
         // Show confirmation dialog
         if self.show_confirmation {
-            egui::Window::new("Confirmation")
+            egui::Window::new(i18n::t("confirm.title"))
                 .collapsible(false)
                 .resizable(false)
                 .show(ctx, |ui| {
-                    ui.label("WARNING: This will permanently erase all data on the selected devices!");
-                    ui.label("There is NO WAY to recover the data after wiping.");
-                    ui.label("Are you sure you want to continue?");
+                    ui.label(RichText::new(i18n::t("confirm.warning_destroy")).color(Color32::RED));
+                    ui.label(i18n::t("confirm.warning_no_recovery"));
+
+                    ui.separator();
+
+                    match self.target_mode {
+                        TargetMode::Disk => {
+                            let devices = self.devices.lock().unwrap();
+                            for device in devices.iter().filter(|d| d.select == SelectStatus::True) {
+                                let size_gb = device.device_size / (1024 * 1024 * 1024);
+                                ui.label(format!(
+                                    "{} - {} ({} GB)",
+                                    device.device_name, device.identity.model_no, size_gb
+                                ));
+                            }
+                        }
+                        TargetMode::FileOrDirectory => {
+                            if let Some(path) = &self.file_target_path {
+                                ui.label(format!(
+                                    "{}{}",
+                                    path.display(),
+                                    if self.file_target_recursive { " (recursive)" } else { "" }
+                                ));
+                            }
+                        }
+                    }
+
+                    ui.separator();
+
+                    ui.checkbox(&mut self.confirm_ack_unrecoverable, i18n::t("confirm.ack_unrecoverable"));
+                    ui.checkbox(&mut self.confirm_ack_correct_disks, i18n::t("confirm.ack_correct_disks"));
+
+                    ui.separator();
+
+                    ui.label(i18n::t("confirm.type_phrase").replace("{}", &self.confirm_phrase));
+                    ui.text_edit_singleline(&mut self.confirm_input);
+
+                    let phrase_matches = self.confirm_input == self.confirm_phrase;
+
+                    let remaining_secs = self
+                        .confirm_countdown_until
+                        .map(|until| until.saturating_duration_since(Instant::now()).as_secs())
+                        .unwrap_or(0);
+                    if remaining_secs > 0 {
+                        ui.label(RichText::new(i18n::t("confirm.please_wait").replace("{}", &remaining_secs.to_string())).color(Color32::YELLOW));
+                    }
+
+                    let can_confirm = phrase_matches
+                        && self.confirm_ack_unrecoverable
+                        && self.confirm_ack_correct_disks
+                        && remaining_secs == 0;
 
                     ui.separator();
 
                     ui.horizontal(|ui| {
-                        if ui.button("Cancel").clicked() {
+                        if ui.button(i18n::t("common.cancel")).clicked() {
                             self.show_confirmation = false;
                         }
 
-                        if ui.button("Yes, Wipe the Devices").clicked() {
+                        let button_label = if remaining_secs > 0 {
+                            i18n::t("confirm.begin_wipe_countdown").replace("{}", &remaining_secs.to_string())
+                        } else {
+                            i18n::t("confirm.begin_wipe")
+                        };
+
+                        if ui.add_enabled(can_confirm, egui::Button::new(button_label)).clicked() {
                             self.show_confirmation = false;
-                            self.start_wiping();
+                            match self.target_mode {
+                                TargetMode::Disk => self.start_wiping(),
+                                TargetMode::FileOrDirectory => self.start_file_erase(),
+                            }
                         }
                     });
                 });
@@ -577,20 +1086,20 @@ impl eframe::App for NwipeApp {
 
         // Show about dialog
         if self.show_about {
-            egui::Window::new("About")
+            egui::Window::new(i18n::t("about.title"))
                 .collapsible(false)
                 .resizable(false)
                 .show(ctx, |ui| {
-                    ui.heading("nwipe - Secure Disk Eraser");
+                    ui.heading(i18n::t("about.heading"));
                     ui.label(version::version_string());
                     ui.label(version::copyright_string());
                     ui.separator();
-                    ui.label("A secure disk wiping utility implemented in Rust.");
-                    ui.label("This program securely erases disks using various methods to ensure data cannot be recovered.");
+                    ui.label(i18n::t("about.description1"));
+                    ui.label(i18n::t("about.description2"));
 
                     ui.separator();
 
-                    if ui.button("Close").clicked() {
+                    if ui.button(i18n::t("common.close")).clicked() {
                         self.show_about = false;
                     }
                 });
@@ -598,69 +1107,141 @@ impl eframe::App for NwipeApp {
 
         // Show help dialog
         if self.show_help {
-            egui::Window::new("Help")
+            egui::Window::new(i18n::t("help.title"))
                 .collapsible(false)
                 .resizable(true)
                 .default_size([500.0, 400.0])
                 .show(ctx, |ui| {
-                    ui.heading("nwipe Help");
+                    ui.heading(i18n::t("help.title"));
                     ui.separator();
 
                     egui::ScrollArea::vertical().show(ui, |ui| {
-                        ui.heading("Wiping Methods");
-                        ui.label("OPS-II (DoD 5220.22-M): Three rounds of wiping with zeros, ones, and random data.");
-                        ui.label("DoD 5220.22-M: One round of wiping with zeros, ones, and random data.");
-                        ui.label("Gutmann: 35 passes with various patterns.");
-                        ui.label("Random: One pass of random data.");
-                        ui.label("Zero: One pass of zeros.");
+                        ui.heading(i18n::t("help.wiping_methods"));
+                        ui.label(i18n::t("help.ops2_desc"));
+                        ui.label(i18n::t("help.dod_desc"));
+                        ui.label(i18n::t("help.gutmann_desc"));
+                        ui.label(i18n::t("help.random_desc"));
+                        ui.label(i18n::t("help.zero_desc"));
 
                         ui.separator();
 
-                        ui.heading("PRNG Options");
-                        ui.label("ISAAC: A cryptographically secure PRNG.");
-                        ui.label("MT19937: Mersenne Twister PRNG.");
-                        ui.label("System Random: The system's default PRNG.");
+                        ui.heading(i18n::t("help.prng_options"));
+                        ui.label(i18n::t("help.isaac_desc"));
+                        ui.label(i18n::t("help.mt19937_desc"));
+                        ui.label(i18n::t("help.chacha_desc"));
+                        ui.label(i18n::t("help.system_random_desc"));
 
                         ui.separator();
 
-                        ui.heading("Safety Warnings");
-                        ui.label("IMPORTANT: nwipe will permanently destroy all data on the selected disks.");
-                        ui.label("There is NO RECOVERY possible after wiping.");
-                        ui.label("Always double-check device names before wiping.");
-                        ui.label("Never wipe your system disk while the system is running from it.");
+                        ui.heading(i18n::t("help.safety_warnings"));
+                        ui.label(i18n::t("help.warning_destroy"));
+                        ui.label(i18n::t("help.warning_no_recovery"));
+                        ui.label(i18n::t("help.warning_check_names"));
+                        ui.label(i18n::t("help.warning_no_system_disk"));
                     });
 
                     ui.separator();
 
-                    if ui.button("Close").clicked() {
+                    if ui.button(i18n::t("common.close")).clicked() {
                         self.show_help = false;
                     }
                 });
         }
 
+        // Show the result of a "Check for Updates" run
+        if let Some(status) = self.update_status.clone() {
+            egui::Window::new("Update Check")
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    match status {
+                        UpdateStatus::Checking => {
+                            ui.label("Checking for updates...");
+                        }
+                        UpdateStatus::UpToDate => {
+                            ui.label(format!("You are running the latest version ({}).", version::VERSION));
+                        }
+                        UpdateStatus::UpdateAvailable { tag, notes, url } => {
+                            ui.label(RichText::new(format!("A new version is available: {}", tag)).strong());
+                            ui.label(format!("You are currently running {}.", version::VERSION));
+                            ui.separator();
+                            egui::ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                                ui.label(notes);
+                            });
+                            ui.separator();
+                            if !url.is_empty() && ui.button("Open Download Page").clicked() {
+                                let _ = std::process::Command::new("xdg-open").arg(&url).spawn();
+                            }
+                        }
+                        UpdateStatus::Failed(reason) => {
+                            ui.label(RichText::new("Update check failed").color(Color32::RED));
+                            ui.label(reason);
+                        }
+                    }
+
+                    ui.separator();
+
+                    if ui.button(i18n::t("common.close")).clicked() {
+                        self.update_status = None;
+                    }
+                });
+        }
+
         // Show settings dialog
         if self.show_settings {
-            egui::Window::new("Settings")
+            egui::Window::new(i18n::t("settings.title"))
                 .collapsible(false)
                 .resizable(false)
                 .show(ctx, |ui| {
-                    ui.heading("Settings");
+                    ui.heading(i18n::t("settings.title"));
                     ui.separator();
 
                     // Add settings here
-                    ui.checkbox(&mut self.autopoweroff, "Power off system when wiping completes");
+                    ui.checkbox(&mut self.autopoweroff, i18n::t("settings.autopoweroff"));
+
+                    ui.horizontal(|ui| {
+                        ui.label(i18n::t("settings.locale"));
+                        egui::ComboBox::from_id_source("locale_select")
+                            .selected_text(self.locale.display_name())
+                            .show_ui(ui, |ui| {
+                                for locale in i18n::Locale::ALL {
+                                    if ui
+                                        .selectable_value(&mut self.locale, locale, locale.display_name())
+                                        .clicked()
+                                    {
+                                        i18n::set_locale(self.locale);
+                                    }
+                                }
+                            });
+                    });
 
                     ui.separator();
 
-                    if ui.button("Close").clicked() {
+                    if ui.button(i18n::t("common.close")).clicked() {
                         self.show_settings = false;
                     }
                 });
         }
     }
+
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        let config = NwipeAppConfig {
+            method: self.method.clone(),
+            prng: self.prng.clone(),
+            rounds: self.rounds,
+            verify: self.verify,
+            autopoweroff: self.autopoweroff,
+            window_width: self.window_size.x,
+            window_height: self.window_size.y,
+            locale: self.locale,
+        };
+
+        eframe::set_value(storage, CONFIG_KEY, &config);
+    }
 }
 
-/// Run the GUI application.
+/// Run the GUI application. Requires eframe's `persistence` feature so
+/// `NwipeApp::save`/`cc.storage` round-trip settings across restarts.
 pub fn run_gui() -> Result<(), eframe::Error> {
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
@@ -0,0 +1,232 @@
+/*
+ *  i18n.rs: Lightweight message-catalog localization for the GUI.
+ *
+ *  Copyright Sebastiaan Koetsier (2025)
+ *
+ *  This program is free software; you can redistribute it and/or modify it under
+ *  the terms of the GNU General Public License as published by the Free Software
+ *  Foundation, version 2.
+ */
+
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+/// A supported UI locale. Every catalog falls back to English for any key
+/// it doesn't translate, so a partial catalog is always safe to ship.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Locale {
+    English,
+    German,
+    Spanish,
+}
+
+impl Locale {
+    /// Every locale the catalog currently covers, in menu order.
+    pub const ALL: [Locale; 3] = [Locale::English, Locale::German, Locale::Spanish];
+
+    /// The name shown in the locale selector, in that language.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Locale::English => "English",
+            Locale::German => "Deutsch",
+            Locale::Spanish => "Espanol",
+        }
+    }
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Locale::English
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref CURRENT_LOCALE: Mutex<Locale> = Mutex::new(Locale::default());
+}
+
+/// Switch the active locale used by every subsequent `t()` lookup.
+pub fn set_locale(locale: Locale) {
+    *CURRENT_LOCALE.lock().unwrap() = locale;
+}
+
+/// The active locale, as last set by `set_locale`.
+pub fn current_locale() -> Locale {
+    *CURRENT_LOCALE.lock().unwrap()
+}
+
+/// Translate `key` into the active locale, falling back to the English
+/// catalog (and finally the key itself) if no translation is found.
+pub fn t(key: &str) -> String {
+    let locale = current_locale();
+
+    if let Some(text) = lookup(locale, key) {
+        return text.to_string();
+    }
+
+    if locale != Locale::English {
+        if let Some(text) = lookup(Locale::English, key) {
+            return text.to_string();
+        }
+    }
+
+    key.to_string()
+}
+
+fn lookup(locale: Locale, key: &str) -> Option<&'static str> {
+    match locale {
+        Locale::English => english(key),
+        Locale::German => german(key),
+        Locale::Spanish => spanish(key),
+    }
+}
+
+fn english(key: &str) -> Option<&'static str> {
+    Some(match key {
+        "menu.file" => "File",
+        "menu.refresh_devices" => "Refresh Devices",
+        "menu.settings" => "Settings",
+        "menu.exit" => "Exit",
+        "menu.help" => "Help",
+        "menu.about" => "About",
+        "menu.check_updates" => "Check for Updates",
+        "action.start_wiping" => "Start Wiping",
+        "action.stop_wiping" => "Stop Wiping",
+        "common.close" => "Close",
+        "about.title" => "About",
+        "about.heading" => "nwipe - Secure Disk Eraser",
+        "about.description1" => "A secure disk wiping utility implemented in Rust.",
+        "about.description2" => "This program securely erases disks using various methods to ensure data cannot be recovered.",
+        "help.title" => "nwipe Help",
+        "help.wiping_methods" => "Wiping Methods",
+        "help.ops2_desc" => "OPS-II (DoD 5220.22-M): Three rounds of wiping with zeros, ones, and random data.",
+        "help.dod_desc" => "DoD 5220.22-M: One round of wiping with zeros, ones, and random data.",
+        "help.gutmann_desc" => "Gutmann: 35 passes with various patterns.",
+        "help.random_desc" => "Random: One pass of random data.",
+        "help.zero_desc" => "Zero: One pass of zeros.",
+        "help.prng_options" => "PRNG Options",
+        "help.isaac_desc" => "ISAAC: A cryptographically secure PRNG.",
+        "help.mt19937_desc" => "MT19937: Mersenne Twister PRNG.",
+        "help.chacha_desc" => "ChaCha20: A fast, cryptographically secure stream cipher PRNG.",
+        "help.system_random_desc" => "System Random: The system's default PRNG.",
+        "help.safety_warnings" => "Safety Warnings",
+        "help.warning_destroy" => "IMPORTANT: nwipe will permanently destroy all data on the selected disks.",
+        "help.warning_no_recovery" => "There is NO RECOVERY possible after wiping.",
+        "help.warning_check_names" => "Always double-check device names before wiping.",
+        "help.warning_no_system_disk" => "Never wipe your system disk while the system is running from it.",
+        "settings.title" => "Settings",
+        "settings.autopoweroff" => "Power off system when wiping completes",
+        "settings.locale" => "Language",
+        "common.cancel" => "Cancel",
+        "confirm.title" => "Confirm Device Wipe",
+        "confirm.warning_destroy" => "WARNING: This will permanently erase all data on the following target(s)!",
+        "confirm.warning_no_recovery" => "There is NO WAY to recover the data after wiping.",
+        "confirm.ack_unrecoverable" => "I understand this data cannot be recovered",
+        "confirm.ack_correct_disks" => "I have verified these are the correct disks",
+        "confirm.type_phrase" => "Type \"{}\" to confirm:",
+        "confirm.please_wait" => "Please wait {} more second(s)...",
+        "confirm.begin_wipe" => "Begin Wipe",
+        "confirm.begin_wipe_countdown" => "Begin Wipe ({})",
+        _ => return None,
+    })
+}
+
+fn german(key: &str) -> Option<&'static str> {
+    Some(match key {
+        "menu.file" => "Datei",
+        "menu.refresh_devices" => "Geraete aktualisieren",
+        "menu.settings" => "Einstellungen",
+        "menu.exit" => "Beenden",
+        "menu.help" => "Hilfe",
+        "menu.about" => "Ueber",
+        "menu.check_updates" => "Nach Updates suchen",
+        "action.start_wiping" => "Loeschen starten",
+        "action.stop_wiping" => "Loeschen stoppen",
+        "common.close" => "Schliessen",
+        "about.title" => "Ueber",
+        "about.heading" => "nwipe - Sicheres Festplattenloeschen",
+        "about.description1" => "Ein in Rust implementiertes Werkzeug zum sicheren Loeschen von Festplatten.",
+        "about.description2" => "Dieses Programm loescht Festplatten sicher mit verschiedenen Methoden, damit Daten nicht wiederherstellbar sind.",
+        "help.title" => "nwipe Hilfe",
+        "help.wiping_methods" => "Loeschmethoden",
+        "help.ops2_desc" => "OPS-II (DoD 5220.22-M): Drei Durchgaenge mit Nullen, Einsen und Zufallsdaten.",
+        "help.dod_desc" => "DoD 5220.22-M: Ein Durchgang mit Nullen, Einsen und Zufallsdaten.",
+        "help.gutmann_desc" => "Gutmann: 35 Durchgaenge mit verschiedenen Mustern.",
+        "help.random_desc" => "Zufall: Ein Durchgang mit Zufallsdaten.",
+        "help.zero_desc" => "Null: Ein Durchgang mit Nullen.",
+        "help.prng_options" => "PRNG-Optionen",
+        "help.isaac_desc" => "ISAAC: Ein kryptografisch sicherer PRNG.",
+        "help.mt19937_desc" => "MT19937: Mersenne-Twister-PRNG.",
+        "help.chacha_desc" => "ChaCha20: Ein schneller, kryptografisch sicherer Stromchiffre-PRNG.",
+        "help.system_random_desc" => "Systemzufall: Der Standard-PRNG des Systems.",
+        "help.safety_warnings" => "Sicherheitshinweise",
+        "help.warning_destroy" => "WICHTIG: nwipe loescht alle Daten auf den ausgewaehlten Festplatten unwiderruflich.",
+        "help.warning_no_recovery" => "Nach dem Loeschen ist KEINE Wiederherstellung moeglich.",
+        "help.warning_check_names" => "Ueberpruefen Sie die Geraetenamen vor dem Loeschen immer genau.",
+        "help.warning_no_system_disk" => "Loeschen Sie niemals die Systemfestplatte, von der das System gerade laeuft.",
+        "settings.title" => "Einstellungen",
+        "settings.autopoweroff" => "System nach Abschluss des Loeschvorgangs ausschalten",
+        "settings.locale" => "Sprache",
+        "common.cancel" => "Abbrechen",
+        "confirm.title" => "Geraeteloeschung bestaetigen",
+        "confirm.warning_destroy" => "WARNUNG: Dies loescht unwiderruflich alle Daten auf den folgenden Ziel(en)!",
+        "confirm.warning_no_recovery" => "Nach dem Loeschen gibt es KEINE Moeglichkeit, die Daten wiederherzustellen.",
+        "confirm.ack_unrecoverable" => "Ich verstehe, dass diese Daten nicht wiederhergestellt werden koennen",
+        "confirm.ack_correct_disks" => "Ich habe bestaetigt, dass dies die richtigen Festplatten sind",
+        "confirm.type_phrase" => "Geben Sie \"{}\" zur Bestaetigung ein:",
+        "confirm.please_wait" => "Bitte warten Sie noch {} Sekunde(n)...",
+        "confirm.begin_wipe" => "Loeschen beginnen",
+        "confirm.begin_wipe_countdown" => "Loeschen beginnen ({})",
+        _ => return None,
+    })
+}
+
+fn spanish(key: &str) -> Option<&'static str> {
+    Some(match key {
+        "menu.file" => "Archivo",
+        "menu.refresh_devices" => "Actualizar dispositivos",
+        "menu.settings" => "Configuracion",
+        "menu.exit" => "Salir",
+        "menu.help" => "Ayuda",
+        "menu.about" => "Acerca de",
+        "menu.check_updates" => "Buscar actualizaciones",
+        "action.start_wiping" => "Iniciar borrado",
+        "action.stop_wiping" => "Detener borrado",
+        "common.close" => "Cerrar",
+        "about.title" => "Acerca de",
+        "about.heading" => "nwipe - Borrador seguro de discos",
+        "about.description1" => "Una utilidad de borrado seguro de discos implementada en Rust.",
+        "about.description2" => "Este programa borra discos de forma segura usando varios metodos para que los datos no se puedan recuperar.",
+        "help.title" => "Ayuda de nwipe",
+        "help.wiping_methods" => "Metodos de borrado",
+        "help.ops2_desc" => "OPS-II (DoD 5220.22-M): Tres rondas de borrado con ceros, unos y datos aleatorios.",
+        "help.dod_desc" => "DoD 5220.22-M: Una ronda de borrado con ceros, unos y datos aleatorios.",
+        "help.gutmann_desc" => "Gutmann: 35 pasadas con varios patrones.",
+        "help.random_desc" => "Aleatorio: Una pasada de datos aleatorios.",
+        "help.zero_desc" => "Cero: Una pasada de ceros.",
+        "help.prng_options" => "Opciones de PRNG",
+        "help.isaac_desc" => "ISAAC: Un PRNG criptograficamente seguro.",
+        "help.mt19937_desc" => "MT19937: PRNG Mersenne Twister.",
+        "help.chacha_desc" => "ChaCha20: Un PRNG rapido y criptograficamente seguro basado en cifrado de flujo.",
+        "help.system_random_desc" => "Aleatorio del sistema: El PRNG predeterminado del sistema.",
+        "help.safety_warnings" => "Advertencias de seguridad",
+        "help.warning_destroy" => "IMPORTANTE: nwipe destruira permanentemente todos los datos de los discos seleccionados.",
+        "help.warning_no_recovery" => "NO hay forma de recuperar los datos despues del borrado.",
+        "help.warning_check_names" => "Verifique siempre los nombres de los dispositivos antes de borrar.",
+        "help.warning_no_system_disk" => "Nunca borre el disco del sistema mientras este en ejecucion desde el.",
+        "settings.title" => "Configuracion",
+        "settings.autopoweroff" => "Apagar el sistema al finalizar el borrado",
+        "settings.locale" => "Idioma",
+        "common.cancel" => "Cancelar",
+        "confirm.title" => "Confirmar borrado de dispositivo",
+        "confirm.warning_destroy" => "ADVERTENCIA: Esto borrara permanentemente todos los datos de los siguientes destinos!",
+        "confirm.warning_no_recovery" => "NO hay forma de recuperar los datos despues del borrado.",
+        "confirm.ack_unrecoverable" => "Entiendo que estos datos no se pueden recuperar",
+        "confirm.ack_correct_disks" => "He verificado que estos son los discos correctos",
+        "confirm.type_phrase" => "Escriba \"{}\" para confirmar:",
+        "confirm.please_wait" => "Espere {} segundo(s) mas...",
+        "confirm.begin_wipe" => "Iniciar borrado",
+        "confirm.begin_wipe_countdown" => "Iniciar borrado ({})",
+        _ => return None,
+    })
+}
@@ -0,0 +1,92 @@
+/*
+ *  journal.rs: On-disk progress journal for resumable wipes.
+ *
+ *  Copyright Sebastiaan Koetsier (2025)
+ *
+ *  This program is free software; you can redistribute it and/or modify it under
+ *  the terms of the GNU General Public License as published by the Free Software
+ *  Foundation, version 2.
+ */
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::logging::{nwipe_log, LogLevel};
+
+/// Directory journals are written under, one sidecar file per device.
+const JOURNAL_DIR: &str = "/var/lib/nwipe/journal";
+
+/// A snapshot of exactly how far the currently running wipe method has
+/// progressed on a device, saved periodically so a crashed or interrupted
+/// run can resume from `byte_offset` instead of starting the whole device
+/// over from scratch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// The wipe method in progress (the same string `run_method` dispatches
+    /// on), so a journal left behind by a different method is never reused.
+    pub method: String,
+    /// Which write/verify call, in the method's fixed call order, was in
+    /// progress. This (not `round_working`/`pass_working`, which a method's
+    /// final pass can reuse purely for display) is what a resumed run
+    /// actually keys off to know which calls to skip.
+    pub call_index: u32,
+    pub round_working: i32,
+    pub pass_working: i32,
+    pub byte_offset: u64,
+}
+
+/// Map a device name (e.g. `/dev/sda`) to its sidecar journal file path.
+fn journal_path(device_name: &str) -> PathBuf {
+    let safe_name = device_name.trim_start_matches('/').replace('/', "_");
+    PathBuf::from(JOURNAL_DIR).join(format!("{}.json", safe_name))
+}
+
+/// Load the journal for `device_name`, if one exists and was recorded
+/// against the same `method` that's about to run. A journal for a different
+/// method is logged and discarded, since its round/pass/offset fields would
+/// be meaningless against this run.
+pub fn load(device_name: &str, method: &str) -> Option<JournalEntry> {
+    let contents = fs::read_to_string(journal_path(device_name)).ok()?;
+    let entry: JournalEntry = serde_json::from_str(&contents).ok()?;
+
+    if entry.method != method {
+        nwipe_log(
+            LogLevel::Info,
+            &format!(
+                "{} has a journal for method '{}', not '{}'; ignoring and starting from scratch",
+                device_name, entry.method, method
+            ),
+        );
+        return None;
+    }
+
+    nwipe_log(
+        LogLevel::Notice,
+        &format!(
+            "{} resuming '{}' from round {} pass {} byte offset {}",
+            device_name, entry.method, entry.round_working, entry.pass_working, entry.byte_offset
+        ),
+    );
+
+    Some(entry)
+}
+
+/// Write (or overwrite) the journal for `device_name`. Failures are not
+/// fatal to the wipe itself, just to resumability, so callers only log them.
+pub fn save(device_name: &str, entry: &JournalEntry) -> io::Result<()> {
+    fs::create_dir_all(JOURNAL_DIR)?;
+
+    let serialized = serde_json::to_string(entry)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("Failed to serialize journal entry: {}", e)))?;
+
+    fs::write(journal_path(device_name), serialized)
+}
+
+/// Remove the journal for `device_name`, once its wipe method has completed
+/// (successfully or not) and there's nothing left to resume.
+pub fn clear(device_name: &str) {
+    let _ = fs::remove_file(journal_path(device_name));
+}
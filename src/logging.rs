@@ -10,18 +10,72 @@
  *  Foundation, version 2.
  */
 
-use std::fs::OpenOptions;
+use std::collections::VecDeque;
+use std::fs::{self, OpenOptions};
 use std::io::Write;
-use std::sync::Mutex;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
 use std::time::{SystemTime, UNIX_EPOCH};
 use std::fmt;
 
 use crate::context::NwipeContext;
 
+/// Maximum number of records kept in the in-memory log ring buffer; the
+/// oldest record is evicted once this is exceeded.
+const LOG_RING_CAPACITY: usize = 2000;
+
+/// Default size-based rotation threshold: rotate once the log file reaches
+/// this many bytes, overridable with `set_log_rotation_limit`.
+const DEFAULT_LOG_ROTATION_LIMIT: u64 = 10 * 1024 * 1024;
+
+/// Number of rotated generations kept alongside the live log file
+/// (`nwipe.log.1` through `nwipe.log.N`); the oldest generation is dropped
+/// once this is exceeded.
+const LOG_ROTATION_GENERATIONS: u32 = 5;
+
 // Global log storage
 lazy_static::lazy_static! {
     static ref LOG_LINES: Mutex<Vec<String>> = Mutex::new(Vec::new());
     static ref LOG_FILE: Mutex<Option<std::fs::File>> = Mutex::new(None);
+    static ref LOG_PATH: Mutex<PathBuf> = Mutex::new(PathBuf::from("/var/log/nwipe.log"));
+    static ref LOG_RING: Arc<Mutex<VecDeque<LogRecord>>> = Arc::new(Mutex::new(VecDeque::with_capacity(LOG_RING_CAPACITY)));
+    static ref LOG_FORMAT: Mutex<LogFormat> = Mutex::new(LogFormat::Text);
+    static ref LOG_LEVEL_THRESHOLD: Mutex<LogLevel> = Mutex::new(LogLevel::Info);
+    static ref LOG_ROTATION_LIMIT: Mutex<u64> = Mutex::new(DEFAULT_LOG_ROTATION_LIMIT);
+    static ref SYSLOG: Mutex<Option<syslog::Logger<syslog::LoggerBackend, syslog::Formatter5424>>> = Mutex::new(None);
+}
+
+/// The output format `nwipe_log` writes lines in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum LogFormat {
+    /// The original "<unix_secs> <LEVEL> <message>" human-readable format.
+    Text,
+    /// One JSON object per line: `{"ts":<unix_secs>,"level":"INFO","msg":"...","device":"...","event":"..."}`,
+    /// with `device`/`event` omitted when not supplied. Suited to feeding
+    /// nwipe runs into log collectors.
+    Json,
+}
+
+impl Default for LogFormat {
+    fn default() -> Self {
+        LogFormat::Text
+    }
+}
+
+/// A single record emitted by `nwipe_log`, kept around so a UI can render
+/// the log without re-parsing formatted text.
+#[derive(Debug, Clone)]
+pub struct LogRecord {
+    pub timestamp: u64,
+    pub level: LogLevel,
+    pub message: String,
+}
+
+/// Return a handle to the shared log ring buffer. Every `nwipe_log` call
+/// pushes a `LogRecord` here; a consumer such as the GUI can clone this
+/// handle once at startup and poll it each frame.
+pub fn log_ring() -> Arc<Mutex<VecDeque<LogRecord>>> {
+    Arc::clone(&LOG_RING)
 }
 
 /// Log levels for nwipe.
@@ -54,14 +108,24 @@ impl fmt::Display for LogLevel {
     }
 }
 
-/// Initialize the logging system.
-pub fn init_logging(_verbose: bool) {
+/// Initialize the logging system with the given output format and log file
+/// path. `verbose` sets the initial minimum level threshold: `Debug` when
+/// true, `Info` otherwise; call `set_log_level_threshold` afterwards to
+/// change it at runtime. When `syslog_enabled` is set, every event at or
+/// above the threshold is also forwarded to the system journal/syslog over
+/// the local `/dev/log` socket, in addition to stdout and `log_path`.
+pub fn init_logging(verbose: bool, format: LogFormat, log_path: impl Into<PathBuf>, syslog_enabled: bool) {
+    *LOG_FORMAT.lock().unwrap() = format;
+    *LOG_LEVEL_THRESHOLD.lock().unwrap() = if verbose { LogLevel::Debug } else { LogLevel::Info };
+
+    let log_path = log_path.into();
+    *LOG_PATH.lock().unwrap() = log_path.clone();
+
     // Set up the log file
-    let log_path = "/var/log/nwipe.log";
     let file_result = OpenOptions::new()
         .create(true)
         .append(true)
-        .open(log_path);
+        .open(&log_path);
 
     match file_result {
         Ok(file) => {
@@ -69,7 +133,22 @@ pub fn init_logging(_verbose: bool) {
             *log_file = Some(file);
         },
         Err(e) => {
-            eprintln!("Warning: Unable to open log file '{}': {}", log_path, e);
+            eprintln!("Warning: Unable to open log file '{}': {}", log_path.display(), e);
+        }
+    }
+
+    // Set up the syslog connection, if requested
+    if syslog_enabled {
+        let formatter = syslog::Formatter5424 {
+            facility: syslog::Facility::LOG_USER,
+            hostname: None,
+            process: "nwipe".into(),
+            pid: std::process::id(),
+        };
+
+        match syslog::unix(formatter) {
+            Ok(logger) => *SYSLOG.lock().unwrap() = Some(logger),
+            Err(e) => eprintln!("Warning: Unable to connect to syslog: {}", e),
         }
     }
 
@@ -82,31 +161,191 @@ pub fn init_logging(_verbose: bool) {
     nwipe_log(LogLevel::Notice, "Nwipe Rust version started");
 }
 
+/// Override the minimum `LogLevel` that gets printed to stdout and persisted
+/// to the log file; messages below this level are still recorded in
+/// `LOG_LINES` for the in-memory summary.
+pub fn set_log_level_threshold(level: LogLevel) {
+    *LOG_LEVEL_THRESHOLD.lock().unwrap() = level;
+}
+
+/// Override the size-based rotation limit (in bytes). The default is 10 MiB.
+pub fn set_log_rotation_limit(bytes: u64) {
+    *LOG_ROTATION_LIMIT.lock().unwrap() = bytes;
+}
+
+/// If the current log file has grown past the rotation limit, shift
+/// `nwipe.log.1..N-1` up to `nwipe.log.2..N` (dropping the oldest
+/// generation), move the live file to `nwipe.log.1`, and reopen a fresh file
+/// at `LOG_PATH`. Called before each write; a no-op when under the limit.
+fn rotate_log_if_needed() {
+    let path = LOG_PATH.lock().unwrap().clone();
+    let limit = *LOG_ROTATION_LIMIT.lock().unwrap();
+
+    let size = match fs::metadata(&path) {
+        Ok(meta) => meta.len(),
+        Err(_) => return,
+    };
+
+    if size < limit {
+        return;
+    }
+
+    let oldest = rotated_path(&path, LOG_ROTATION_GENERATIONS);
+    let _ = fs::remove_file(&oldest);
+
+    for generation in (1..LOG_ROTATION_GENERATIONS).rev() {
+        let from = rotated_path(&path, generation);
+        let to = rotated_path(&path, generation + 1);
+        let _ = fs::rename(&from, &to);
+    }
+
+    let _ = fs::rename(&path, rotated_path(&path, 1));
+
+    let file_result = OpenOptions::new().create(true).append(true).open(&path);
+    match file_result {
+        Ok(file) => *LOG_FILE.lock().unwrap() = Some(file),
+        Err(e) => eprintln!("Warning: Unable to reopen log file '{}' after rotation: {}", path.display(), e),
+    }
+}
+
+/// Build the path for rotation generation `n`, e.g. `nwipe.log.1`.
+fn rotated_path(base: &std::path::Path, n: u32) -> PathBuf {
+    let mut name = base.file_name().map(|n| n.to_os_string()).unwrap_or_default();
+    name.push(format!(".{}", n));
+    base.with_file_name(name)
+}
+
 /// Log a message to the nwipe log.
 pub fn nwipe_log(level: LogLevel, message: &str) {
+    nwipe_log_event(level, message, None, None);
+}
+
+/// Log a message to the nwipe log, tagged with an optional device name and
+/// an optional event tag (e.g. `"verify_fail"`). In `LogFormat::Json` mode
+/// these become queryable fields instead of being folded into free text; in
+/// `LogFormat::Text` mode they are not rendered, since the text format is a
+/// fixed `<unix_secs> <LEVEL> <message>` line.
+pub fn nwipe_log_event(level: LogLevel, message: &str, device: Option<&str>, event: Option<&str>) {
     // Get the current time
     let now = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap_or_default()
         .as_secs();
 
-    // Format the log message
-    let log_message = format!("{} {} {}", now, level, message);
+    // Format the log message in the selected output format. The text path
+    // must stay byte-for-byte identical to the original format.
+    let format = *LOG_FORMAT.lock().unwrap();
+    let log_message = match format {
+        LogFormat::Text => format!("{} {} {}", now, level, message),
+        LogFormat::Json => json_log_line(now, level, message, device, event),
+    };
+
+    // Messages below the configured threshold are suppressed on stdout/file,
+    // but still retained below in LOG_LINES/LOG_RING for the in-memory
+    // summary.
+    let above_threshold = level <= *LOG_LEVEL_THRESHOLD.lock().unwrap();
 
-    // Print to stdout
-    println!("{}", log_message);
+    if above_threshold {
+        println!("{}", log_message);
+    }
 
     // Store in memory
     let mut log_lines = LOG_LINES.lock().unwrap();
     log_lines.push(log_message.clone());
+    drop(log_lines);
+
+    // Store in the ring buffer consumed by the GUI, evicting the oldest
+    // record once the buffer is full.
+    let mut ring = LOG_RING.lock().unwrap();
+    if ring.len() >= LOG_RING_CAPACITY {
+        ring.pop_front();
+    }
+    ring.push_back(LogRecord { timestamp: now, level, message: message.to_string() });
+    drop(ring);
 
     // Write to log file if available
+    if above_threshold {
+        rotate_log_if_needed();
+
+        if let Ok(log_file) = LOG_FILE.lock() {
+            if let Some(mut file) = log_file.as_ref() {
+                let _ = writeln!(file, "{}", log_message);
+                let _ = file.flush();
+            }
+        }
+
+        // Forward to syslog, if connected, using the free-text message
+        // rather than `log_message`: the syslog transport already attaches
+        // its own timestamp/severity/facility framing per RFC 5424.
+        if let Ok(mut syslog) = SYSLOG.lock() {
+            if let Some(logger) = syslog.as_mut() {
+                let _ = match level {
+                    LogLevel::Fatal => logger.crit(message),
+                    LogLevel::Error => logger.err(message),
+                    LogLevel::Warning => logger.warning(message),
+                    LogLevel::Notice => logger.notice(message),
+                    LogLevel::Info => logger.info(message),
+                    LogLevel::Debug => logger.debug(message),
+                };
+            }
+        }
+    }
+}
+
+/// Flush and close every log sink before the process exits. The file sink
+/// already flushes on every write, so this mainly records how many lines
+/// this run produced and tears down the syslog connection cleanly rather
+/// than silently dropping it.
+pub fn flush_logs() {
+    let line_count = LOG_LINES.lock().unwrap().len();
+    nwipe_log(LogLevel::Info, &format!("Flushing {} buffered log lines before exit", line_count));
+
     if let Ok(log_file) = LOG_FILE.lock() {
-        if let Some(mut file) = log_file.as_ref() {
-            let _ = writeln!(file, "{}", log_message);
+        if let Some(file) = log_file.as_ref() {
             let _ = file.flush();
         }
     }
+
+    *SYSLOG.lock().unwrap() = None;
+}
+
+/// Build one JSON object line for `LogFormat::Json`, omitting `device`/`event`
+/// when not supplied.
+fn json_log_line(ts: u64, level: LogLevel, message: &str, device: Option<&str>, event: Option<&str>) -> String {
+    let mut line = format!(
+        "{{\"ts\":{},\"level\":\"{}\",\"msg\":\"{}\"",
+        ts,
+        level,
+        json_escape(message)
+    );
+
+    if let Some(device) = device {
+        line.push_str(&format!(",\"device\":\"{}\"", json_escape(device)));
+    }
+
+    if let Some(event) = event {
+        line.push_str(&format!(",\"event\":\"{}\"", json_escape(event)));
+    }
+
+    line.push('}');
+    line
+}
+
+/// Escape a string for embedding as a JSON string value.
+fn json_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
 }
 
 /// Log system information.
@@ -151,34 +390,41 @@ pub fn log_summary(contexts: &[NwipeContext], count: usize) {
             format!("Wipe failed with error code {}", context.result)
         };
 
-        // Log the device result
-        nwipe_log(
+        // Log the device result, tagged so it's a queryable record in
+        // LogFormat::Json mode rather than just a decorative banner line.
+        nwipe_log_event(
             LogLevel::Info,
             &format!(
                 "Device: {} - {}",
                 context.device_name,
                 result_msg
-            )
+            ),
+            Some(&context.device_name),
+            Some("wipe_result"),
         );
 
         // Log additional information if available
         if !context.identity.serial_no.is_empty() {
-            nwipe_log(
+            nwipe_log_event(
                 LogLevel::Info,
                 &format!(
                     "  Serial Number: {}",
                     context.identity.serial_no
-                )
+                ),
+                Some(&context.device_name),
+                Some("device_info"),
             );
         }
 
         if !context.identity.model_no.is_empty() {
-            nwipe_log(
+            nwipe_log_event(
                 LogLevel::Info,
                 &format!(
                     "  Model: {}",
                     context.identity.model_no
-                )
+                ),
+                Some(&context.device_name),
+                Some("device_info"),
             );
         }
 
@@ -189,24 +435,28 @@ pub fn log_summary(contexts: &[NwipeContext], count: usize) {
             let minutes = (duration % 3600) / 60;
             let seconds = duration % 60;
 
-            nwipe_log(
+            nwipe_log_event(
                 LogLevel::Info,
                 &format!(
                     "  Duration: {:02}:{:02}:{:02}",
                     hours, minutes, seconds
-                )
+                ),
+                Some(&context.device_name),
+                Some("wipe_duration"),
             );
         }
 
         if context.bytes_total > 0 {
             // Convert to MB for display
             let mb_total = context.bytes_total / (1024 * 1024);
-            nwipe_log(
+            nwipe_log_event(
                 LogLevel::Info,
                 &format!(
                     "  Total bytes processed: {} MB",
                     mb_total
-                )
+                ),
+                Some(&context.device_name),
+                Some("wipe_bytes"),
             );
         }
     }
@@ -21,22 +21,32 @@
  *
  */
 
+mod cancel;
+mod certificate;
 mod context;
 mod device;
+mod disk_manage;
+mod erase;
+mod file_erase;
 mod gui;
 mod gui_app;
+mod i18n;
+mod journal;
 mod logging;
 mod method;
 mod options;
+mod power;
 mod prng;
+mod report;
+mod update_check;
 mod version;
 
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::{self, Read, Seek, SeekFrom, Write};
 use std::os::unix::io::{AsRawFd, RawFd};
 use std::path::Path;
-use std::process::Command;
-use std::sync::{Arc, Mutex};
+use std::sync::{mpsc, Arc, Mutex};
 use std::thread;
 use std::time::{Duration, Instant};
 
@@ -47,16 +57,13 @@ use nix::sys::stat::Mode;
 use nix::unistd::close;
 use signal_hook::{consts::*, iterator::Signals};
 
+use crate::cancel::CancelHandles;
 use crate::context::{NwipeContext, SelectStatus};
 use crate::device::device_scan;
 use crate::gui::gui_init;
-use crate::logging::nwipe_log;
+use crate::logging::{convert_seconds_to_hours_minutes_seconds, nwipe_log};
 use crate::options::{NwipeOptions, parse_options};
 
-// Global variables
-static mut TERMINATE_SIGNAL: bool = false;
-static mut USER_ABORT: bool = false;
-
 const NWIPE_KNOB_ENTROPY: &str = "/dev/urandom";
 const NWIPE_KNOB_SLEEP: u8 = 1;
 
@@ -65,7 +72,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let options = parse_options();
 
     // Initialize logging
-    logging::init_logging(options.verbose);
+    logging::init_logging(options.verbose, options.log_format, options.log_file.clone(), options.syslog);
+
+    // Shared cancellation flags, cloned into the signal thread, the GUI,
+    // and every wipe thread so they can all unwind cleanly without unsafe
+    // global state.
+    let cancel = CancelHandles::new();
 
     // Check if we should use the modern GUI
     if options.modern_gui {
@@ -87,7 +99,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Scan for devices or use provided device names
     let nwipe_enumerated = if options.device_names.is_empty() {
         // Scan for devices
-        match device_scan(&mut contexts) {
+        match device_scan(&mut contexts, options.include_in_use) {
             Ok(count) => {
                 if count == 0 {
                     nwipe_log(logging::LogLevel::Info, "Storage devices not found.");
@@ -124,7 +136,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     };
 
-    if unsafe { TERMINATE_SIGNAL } {
+    if cancel.terminate.is_set() {
         cleanup();
         return Ok(());
     }
@@ -148,21 +160,36 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     nwipe_log(logging::LogLevel::Notice, &format!("Opened entropy source '{}'.", NWIPE_KNOB_ENTROPY));
 
+    // Shared table of the most recent progress report from each wipe thread,
+    // keyed by device name. Wipe threads publish into it via `progress_tx`;
+    // the signal thread reads it on SIGUSR1 so an operator can poke a
+    // headless `--nogui --autonuke` run for progress without a TUI.
+    let progress_map: Arc<Mutex<HashMap<String, method::ProgressUpdate>>> = Arc::new(Mutex::new(HashMap::new()));
+    let (progress_tx, progress_rx) = mpsc::channel::<method::ProgressUpdate>();
+    let progress_aggregator_map = progress_map.clone();
+    let progress_aggregator = thread::spawn(move || {
+        for update in progress_rx.iter() {
+            progress_aggregator_map.lock().unwrap().insert(update.device_name.clone(), update);
+        }
+    });
+
     // Set up signal handling
     let mut signals = Signals::new(&[SIGHUP, SIGTERM, SIGQUIT, SIGINT, SIGUSR1])?;
+    let signal_cancel = cancel.clone();
+    let signal_progress_map = progress_map.clone();
     let signal_thread = thread::spawn(move || {
         for sig in signals.forever() {
             match sig {
                 SIGUSR1 => {
-                    // Log current status
-                    // TODO: Implement status logging
+                    // Dump a one-line status report per device to the log
+                    // and stderr, so an operator can `kill -USR1` a headless
+                    // run to harvest progress.
+                    report_status(&signal_progress_map);
                 },
                 SIGHUP | SIGINT | SIGQUIT | SIGTERM => {
                     // Set termination flag
-                    unsafe {
-                        TERMINATE_SIGNAL = true;
-                        USER_ABORT = true;
-                    }
+                    signal_cancel.terminate.set();
+                    signal_cancel.user_abort.set();
                     break;
                 },
                 _ => {},
@@ -170,6 +197,24 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     });
 
+    // If --seed-from was given, open it once up front; each device below
+    // reads its own 32-byte chunk from it so a reproducible test run still
+    // gives every drive a distinct seed. Falling back to the entropy source
+    // on open failure matches how other per-run setup failures here degrade
+    // rather than abort.
+    let mut seed_file = options.seed_from.as_ref().and_then(|path| {
+        match File::open(path) {
+            Ok(file) => Some(file),
+            Err(e) => {
+                nwipe_log(
+                    logging::LogLevel::Fatal,
+                    &format!("Unable to open --seed-from file {}: {}, falling back to entropy source", path.display(), e),
+                );
+                None
+            }
+        }
+    });
+
     // Set specific nwipe options for each device
     for context in &mut contexts {
         // Set the entropy source
@@ -184,8 +229,61 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
         // Set the PRNG implementation
         context.prng = options.prng.clone();
-        // Initialize PRNG state and seed
-        // TODO: Implement PRNG initialization
+
+        // Set the wipe method, kept distinct from the PRNG implementation
+        // above so run_method_with_progress dispatches on the method the
+        // user actually selected via -m/--method.
+        context.wipe_method = options.method.clone();
+
+        // Seed the PRNG now, from a dedicated 256-bit read, so each device
+        // gets an independently seeded instance and parallel wipes don't
+        // emit correlated streams. `--seed-from` substitutes a fixed file
+        // for reproducible test runs; otherwise the seed comes from the
+        // entropy source opened above.
+        const SEED_LEN: usize = 32;
+        let seed_result = match seed_file.as_mut() {
+            Some(file) => {
+                let mut bytes = vec![0u8; SEED_LEN];
+                file.read_exact(&mut bytes)
+                    .map(|_| context::PrngSeed { length: SEED_LEN, s: bytes })
+                    .map_err(|e| io::Error::new(e.kind(), format!("--seed-from file exhausted or unreadable: {}", e)))
+            }
+            None => prng::seed_from_entropy_fd(context.entropy_fd, SEED_LEN),
+        };
+
+        match seed_result {
+            Ok(seed) => {
+                context.prng_seed = seed;
+                nwipe_log(
+                    logging::LogLevel::Notice,
+                    &format!(
+                        "{} PRNG '{}' seeded from {}",
+                        context.device_name,
+                        context.prng,
+                        if seed_file.is_some() { "--seed-from file" } else { "entropy source" }
+                    ),
+                );
+            }
+            Err(e) => {
+                nwipe_log(logging::LogLevel::Fatal, &format!("{} failed to seed PRNG: {}", context.device_name, e));
+                context.select = SelectStatus::Disabled;
+                continue;
+            }
+        }
+
+        // Warn when an overwrite-based method is about to be used on
+        // non-rotational media, where repeated passes are both slow and
+        // unreliable due to wear-levelling remapping.
+        if context.media_type == context::MediaType::SolidState && device::is_overwrite_method(&options.method) {
+            nwipe_log(
+                logging::LogLevel::Warning,
+                &format!(
+                    "{} is solid-state media; method '{}' overwrites logical sectors only \
+                     and may not clear remapped flash blocks. Consider a discard/secure-erase method instead.",
+                    context.device_name, options.method
+                ),
+            );
+        }
     }
 
     // Start the UI interface if not in nogui mode
@@ -204,7 +302,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             cleanup();
             return Ok(());
         } else {
-            gui::gui_select(nwipe_enumerated, &mut contexts);
+            gui::gui_select(nwipe_enumerated, &mut contexts, &cancel.user_abort);
         }
     }
 
@@ -219,9 +317,16 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Start wiping threads if user hasn't aborted
     let mut wipe_threads_started = false;
-    let mut thread_handles = Vec::new();
+    let mut thread_handles: Vec<(String, thread::JoinHandle<()>)> = Vec::new();
 
-    if !unsafe { USER_ABORT } {
+    // Each wipe thread runs on its own clone of the context, moved into the
+    // thread; it reports the finished context (start/end time, bytes
+    // written, result, etc.) back here by pushing it into this shared vec
+    // rather than mutating the original `selected_contexts` entry, which it
+    // has no access to once moved.
+    let finished_contexts: Arc<Mutex<Vec<NwipeContext>>> = Arc::new(Mutex::new(Vec::new()));
+
+    if !cancel.user_abort.is_set() {
         for context in &mut selected_contexts {
             // Initialize context for wiping
             context.spinner_idx = 0;
@@ -234,8 +339,45 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 Ok(file) => {
                     context.device_fd = file.as_raw_fd();
 
-                    // Get device information
-                    // TODO: Implement device stat and size retrieval
+                    // If an HPA/DCO was detected during scanning and the user
+                    // opted in to unhiding it, remove it now so the wipe
+                    // covers the drive's full native capacity.
+                    if options.unhide_hpa && context.hidden_sectors > 0 {
+                        match device::clear_hpa(context.device_fd, context.native_max_lba) {
+                            Ok(()) => {
+                                nwipe_log(
+                                    logging::LogLevel::Notice,
+                                    &format!(
+                                        "{} HPA/DCO removed, {} previously hidden sectors are now accessible",
+                                        context.device_name, context.hidden_sectors
+                                    ),
+                                );
+                            }
+                            Err(e) => {
+                                nwipe_log(
+                                    logging::LogLevel::Warning,
+                                    &format!("{} failed to remove HPA/DCO: {}", context.device_name, e),
+                                );
+                            }
+                        }
+                    }
+
+                    // Re-read the block-device geometry against the freshly
+                    // opened read/write fd, in case it changed since the
+                    // initial scan (e.g. an HPA/DCO was just removed above).
+                    if let Err(e) = device::get_device_size(context.device_fd, context) {
+                        nwipe_log(logging::LogLevel::Fatal,
+                                 &format!("{} failed to read device size: {}", context.device_name, e));
+                        context.select = SelectStatus::Disabled;
+                        continue;
+                    }
+
+                    if context.device_size == 0 {
+                        nwipe_log(logging::LogLevel::Fatal,
+                                 &format!("{} reports zero size, refusing to wipe", context.device_name));
+                        context.select = SelectStatus::Disabled;
+                        continue;
+                    }
 
                     // Print serial number if available
                     if !context.identity.serial_no.is_empty() {
@@ -244,19 +386,56 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     }
 
                     // Get device sector and block size
-                    // TODO: Implement ioctl calls for device information
-
-                    // Get device size
-                    // TODO: Implement device size retrieval
+                    if let Err(e) = device::get_device_sector_block_size(context.device_fd, context) {
+                        nwipe_log(logging::LogLevel::Warning,
+                                 &format!("{} failed to read sector/block size: {}", context.device_name, e));
+                    }
 
                     // Start wiping thread
                     let context_clone = context.clone();
+                    let thread_cancel = cancel.terminate.clone();
+                    let thread_progress_tx = progress_tx.clone();
+                    let no_resume = options.no_resume;
+                    let thread_finished_contexts = finished_contexts.clone();
+                    let device_name = context.device_name.clone();
                     let handle = thread::spawn(move || {
                         // Call the selected wiping method
-                        method::run_method(&context_clone);
+                        let (result, final_context) = method::run_method_with_progress(&context_clone, Some(thread_progress_tx), &thread_cancel, no_resume);
+
+                        // On success, re-read the device to produce a
+                        // verification hash and an erasure certificate.
+                        if result == 0 {
+                            match certificate::verify_and_certify(&final_context, &final_context.wipe_method) {
+                                Ok(cert) => {
+                                    let cert_path = format!(
+                                        "/var/log/nwipe-{}.json",
+                                        final_context.device_name.replace('/', "_")
+                                    );
+                                    if let Err(e) = certificate::write_certificate(&cert, &cert_path) {
+                                        nwipe_log(
+                                            logging::LogLevel::Warning,
+                                            &format!("Failed to write erasure certificate for {}: {}", final_context.device_name, e),
+                                        );
+                                    } else {
+                                        nwipe_log(
+                                            logging::LogLevel::Notice,
+                                            &format!("Erasure certificate written to {}", cert_path),
+                                        );
+                                    }
+                                }
+                                Err(e) => {
+                                    nwipe_log(
+                                        logging::LogLevel::Warning,
+                                        &format!("Verification pass for {} failed: {}", final_context.device_name, e),
+                                    );
+                                }
+                            }
+                        }
+
+                        thread_finished_contexts.lock().unwrap().push(final_context);
                     });
 
-                    thread_handles.push(handle);
+                    thread_handles.push((device_name, handle));
                     wipe_threads_started = true;
                 },
                 Err(e) => {
@@ -272,33 +451,45 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Start GUI status thread if not in nogui mode
     let gui_thread = if !options.nogui {
         let selected_contexts_clone = selected_contexts.clone();
+        let gui_cancel = cancel.terminate.clone();
         Some(thread::spawn(move || {
-            gui::gui_status(&selected_contexts_clone, nwipe_selected);
+            gui::gui_status(&selected_contexts_clone, nwipe_selected, &gui_cancel);
         }))
     } else {
         None
     };
 
-    // Wait for all wiping threads to finish
-    let mut i = 0;
-    while i < nwipe_selected && !unsafe { TERMINATE_SIGNAL } {
-        if i == nwipe_selected {
-            break;
+    // Wait for all wiping threads to finish. Each thread polls its own clone
+    // of `cancel.terminate` between write chunks, so a blocking join here
+    // returns either on normal completion or promptly after an external
+    // SIGTERM/SIGINT (handled by `signal_thread`) asks it to unwind -
+    // no need to busy-poll a status field the thread has no way to update.
+    let mut joined_devices = Vec::new();
+    for (device_name, handle) in thread_handles.drain(..) {
+        if let Err(_e) = handle.join() {
+            nwipe_log(logging::LogLevel::Warning,
+                     &format!("Wipe thread for device {} panicked", device_name));
         }
+        joined_devices.push(device_name);
+    }
 
-        if selected_contexts[i].wipe_status != 0 {
-            i = 0;
-        } else {
-            i += 1;
-            continue;
+    // Merge each thread's finished context (start/end time, bytes written,
+    // result, etc.) back into `selected_contexts`, matched by device name so
+    // this stays correct even though some contexts never got a thread
+    // spawned (e.g. ones that failed to open or read their size above).
+    for final_context in finished_contexts.lock().unwrap().drain(..) {
+        if let Some(context) = selected_contexts
+            .iter_mut()
+            .find(|c| c.device_name == final_context.device_name)
+        {
+            *context = final_context;
         }
-        thread::sleep(Duration::from_secs(1));
     }
 
-    // Wait for user input if not in nowait mode and not set to autopoweroff
-    if !unsafe { TERMINATE_SIGNAL } && !options.nowait && !options.autopoweroff {
+    // Wait for user input if not in nowait mode and no power action is set
+    if !cancel.terminate.is_set() && !options.nowait && options.power_action.is_none() {
         loop {
-            if unsafe { TERMINATE_SIGNAL } {
+            if cancel.terminate.is_set() {
                 break;
             }
             thread::sleep(Duration::from_secs(1));
@@ -309,14 +500,18 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         nwipe_log(logging::LogLevel::Info, "Exit in progress");
     }
 
-    // Request cancellation of wipe threads
-    for (i, handle) in thread_handles.iter().enumerate() {
-        if options.verbose {
+    // Request cancellation of wipe threads. Every thread holds a clone of
+    // `cancel.terminate` and polls it between write chunks, so setting it
+    // once here unwinds any wipe that's still in flight. The wipe threads
+    // themselves were already joined above, so this only matters for the
+    // GUI status thread joined below.
+    cancel.terminate.set();
+    if options.verbose {
+        for device_name in &joined_devices {
             nwipe_log(logging::LogLevel::Info,
-                     &format!("Requesting wipe thread cancellation for {}", selected_contexts[i].device_name));
-            nwipe_log(logging::LogLevel::Info, "Please wait..");
+                     &format!("Requesting wipe thread cancellation for {}", device_name));
         }
-        // TODO: Implement thread cancellation
+        nwipe_log(logging::LogLevel::Info, "Please wait..");
     }
 
     // Kill the GUI thread
@@ -340,24 +535,27 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         gui::gui_free();
     }
 
-    // Wait for wipe threads to finish
-    for (i, handle) in thread_handles.iter().enumerate() {
-        if let Err(e) = handle.join() {
-            nwipe_log(logging::LogLevel::Warning, "Error when waiting for wipe thread to cancel.");
-        }
+    // Close the device file descriptor for every context a wipe thread was
+    // actually started on; wipe threads themselves were already joined above.
+    for device_name in &joined_devices {
+        if let Some(context) = selected_contexts.iter().find(|c| &c.device_name == device_name) {
+            if options.verbose {
+                nwipe_log(logging::LogLevel::Info,
+                         &format!("Wipe thread for device {} has finished", context.device_name));
+            }
 
-        if options.verbose {
-            nwipe_log(logging::LogLevel::Info,
-                     &format!("Wipe thread for device {} has been cancelled", selected_contexts[i].device_name));
+            close(context.device_fd).unwrap_or_else(|e| {
+                nwipe_log(logging::LogLevel::Warning,
+                         &format!("Error closing device {}: {}", context.device_name, e));
+            });
         }
-
-        // Close device file descriptor
-        close(selected_contexts[i].device_fd).unwrap_or_else(|e| {
-            nwipe_log(logging::LogLevel::Warning,
-                     &format!("Error closing device {}: {}", selected_contexts[i].device_name, e));
-        });
     }
 
+    // Every wipe thread's progress sender clone is dropped by now; drop the
+    // original so the aggregator's channel closes and it can join.
+    drop(progress_tx);
+    let _ = progress_aggregator.join();
+
     // Check for errors and set return status
     let mut return_status = 0;
 
@@ -389,33 +587,110 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Generate and send the drive status summary to the log
     logging::log_summary(&selected_contexts, nwipe_selected);
 
+    // Write a batch erasure certificate covering every wiped device. If a
+    // PKCS#11 hardware token is configured via environment variables, sign
+    // the certificate with it; otherwise fall back to an unsigned one
+    // rather than skipping certificate generation entirely.
+    let signing_token = match (
+        std::env::var("NWIPE_PKCS11_MODULE"),
+        std::env::var("NWIPE_PKCS11_SLOT"),
+        std::env::var("NWIPE_PKCS11_PIN"),
+        std::env::var("NWIPE_PKCS11_KEY_LABEL"),
+    ) {
+        (Ok(module), Ok(slot), Ok(pin), Ok(key_label)) => match slot.parse() {
+            Ok(slot) => match certificate::SigningToken::open(&module, slot, &pin, &key_label) {
+                Ok(token) => Some(token),
+                Err(e) => {
+                    nwipe_log(logging::LogLevel::Warning, &format!("Could not open PKCS#11 signing token: {}", e));
+                    None
+                }
+            },
+            Err(e) => {
+                nwipe_log(logging::LogLevel::Warning, &format!("Invalid NWIPE_PKCS11_SLOT \"{}\": {}", slot, e));
+                None
+            }
+        },
+        _ => None,
+    };
+
+    let certificate_result = match &signing_token {
+        Some(token) => certificate::write_signed_certificate(&selected_contexts, nwipe_selected, token),
+        None => certificate::write_unsigned_certificate(&selected_contexts, nwipe_selected),
+    };
+
+    if let Err(e) = certificate_result {
+        nwipe_log(logging::LogLevel::Error, &format!("Failed to write batch erasure certificate: {}", e));
+    }
+
+    // If requested, also write a structured completion report a compliance
+    // workflow can parse directly instead of scraping the log.
+    if let Some(report_path) = &options.report {
+        let run_report = report::build_report(&selected_contexts, nwipe_selected, &options.method);
+        match report::write_report(&run_report, report_path, options.report_format) {
+            Ok(()) => nwipe_log(logging::LogLevel::Notice, &format!("Wrote completion report to {}", report_path.display())),
+            Err(e) => nwipe_log(logging::LogLevel::Error, &format!("Failed to write completion report to {}: {}", report_path.display(), e)),
+        }
+    }
+
     if return_status == 0 {
         nwipe_log(logging::LogLevel::Info, "Nwipe successfully exited.");
     }
 
     cleanup();
 
-    check_for_autopoweroff(&options);
+    if let Some(action) = options.power_action {
+        power::execute(action, options.power_delay);
+    }
 
     Ok(())
 }
 
-fn cleanup() -> i32 {
-    // TODO: Implement cleanup functionality
-    // Print logs held in memory
-    // Deallocate memory used by logging
+/// Log a one-line status report for every device with a progress update on
+/// record, in response to SIGUSR1. Each line goes to the log (and, per
+/// `LOG_LEVEL_THRESHOLD`, stdout) via `nwipe_log`, and unconditionally to
+/// stderr, so a headless `--nogui --autonuke` run can be polled with
+/// `kill -USR1` without relying on the log's verbosity setting.
+fn report_status(progress_map: &Arc<Mutex<HashMap<String, method::ProgressUpdate>>>) {
+    let map = progress_map.lock().unwrap();
+
+    if map.is_empty() {
+        nwipe_log(logging::LogLevel::Info, "Status: no wipe progress reported yet");
+        return;
+    }
 
-    0
-}
+    for update in map.values() {
+        let bytes_remaining = update.bytes_total.saturating_sub(update.bytes_written);
+        let throughput_mb = update.throughput_bps as f64 / (1024.0 * 1024.0);
 
-fn check_for_autopoweroff(options: &NwipeOptions) {
-    if options.autopoweroff {
-        let cmd = "shutdown -P +1 \"System going down in one minute\"";
-        match Command::new("sh").arg("-c").arg(cmd).output() {
-            Ok(_) => {},
-            Err(_) => {
-                nwipe_log(logging::LogLevel::Info, &format!("Failed to autopoweroff with command: {}", cmd));
-            }
-        }
+        let mut eta_hours = 0;
+        let mut eta_minutes = 0;
+        let mut eta_seconds = 0;
+        let eta_secs = if update.throughput_bps > 0 {
+            (bytes_remaining / update.throughput_bps) as i64
+        } else {
+            -1
+        };
+        convert_seconds_to_hours_minutes_seconds(eta_secs, &mut eta_hours, &mut eta_minutes, &mut eta_seconds);
+
+        let line = format!(
+            "Status: {} - {} - {:.2}% - {:.2} MB/s - {} bytes remaining - ETA {:02}:{:02}:{:02}",
+            update.device_name,
+            update.pass_label,
+            update.percent,
+            throughput_mb,
+            bytes_remaining,
+            eta_hours,
+            eta_minutes,
+            eta_seconds
+        );
+
+        nwipe_log(logging::LogLevel::Info, &line);
+        eprintln!("{}", line);
     }
 }
+
+fn cleanup() -> i32 {
+    logging::flush_logs();
+
+    0
+}
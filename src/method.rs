@@ -13,24 +13,82 @@
 use std::fs::{File, OpenOptions};
 use std::io::{self, Read, Seek, SeekFrom, Write};
 use std::os::unix::io::{AsRawFd, FromRawFd, RawFd};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender, SyncSender};
 use std::sync::Arc;
 use std::thread;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use nix::fcntl::{fcntl, FcntlArg, OFlag};
+use nix::libc;
 use nix::sys::stat::Mode;
 use nix::unistd::{close, fsync, lseek, Whence};
-
-use crate::context::{NwipeContext, PassType};
-use crate::logging::{nwipe_log, LogLevel};
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+
+use crate::cancel::CancelFlag;
+use crate::context::{NwipeContext, PassType, PrngSeed};
+use crate::device::BLKZEROOUT;
+use crate::erase;
+use crate::journal;
+use crate::logging::{nwipe_log, nwipe_log_event, LogLevel};
 use crate::prng;
 
 // Buffer size for wiping (4 MiB)
 const NWIPE_KNOB_BUFSIZE: usize = 4 * 1024 * 1024;
 
+// Chunk size for the BLKZEROOUT fast path (1 GiB), so progress, ETA and
+// cancellation are still serviced regularly on large devices.
+const BLKZEROOUT_CHUNK: u64 = 1024 * 1024 * 1024;
+
+/// A live progress report emitted periodically by a wipe thread.
+#[derive(Debug, Clone)]
+pub struct ProgressUpdate {
+    pub device_name: String,
+    pub round: i32,
+    pub total_rounds: i32,
+    pub pass_label: String,
+    pub percent: f64,
+    pub bytes_written: u64,
+    pub bytes_total: u64,
+    pub throughput_bps: u64,
+}
+
+/// The channel a wipe thread uses to report `ProgressUpdate`s, if any.
+pub type ProgressSink = Option<Sender<ProgressUpdate>>;
+
+/// Build and send a `ProgressUpdate` from the current context state.
+fn report_progress(progress_tx: &ProgressSink, context: &NwipeContext) {
+    let Some(tx) = progress_tx else { return };
+
+    let pass_label = format!("{:?} pass {}/{}", context.pass_type, context.pass_working, context.pass_count);
+
+    let _ = tx.send(ProgressUpdate {
+        device_name: context.device_name.clone(),
+        round: context.round_working,
+        total_rounds: context.round_count,
+        pass_label,
+        percent: context.round_percent,
+        bytes_written: context.bytes_written,
+        bytes_total: context.bytes_total,
+        throughput_bps: context.throughput,
+    });
+}
+
 /// Run the selected wiping method on the device.
-pub fn run_method(context: &NwipeContext) -> i32 {
+pub fn run_method(context: &NwipeContext, cancel: &CancelFlag) -> i32 {
+    run_method_with_progress(context, None, cancel, false).0
+}
+
+/// Run the selected wiping method on the device, reporting live progress
+/// over `progress_tx` if one is supplied. Unless `no_resume` is set, a
+/// progress journal left behind by a prior interrupted run of the same
+/// method on this device is picked up and resumed from.
+///
+/// Returns the result code together with the finished context (start/end
+/// time, bytes written, result, etc. all recorded on it), since the caller
+/// typically runs this on a throwaway clone moved into a worker thread and
+/// needs this updated copy back to report accurate status afterwards.
+pub fn run_method_with_progress(context: &NwipeContext, progress_tx: ProgressSink, cancel: &CancelFlag, no_resume: bool) -> (i32, NwipeContext) {
     // Set up a safe copy of the context that we can modify
     let mut ctx = context.clone();
 
@@ -49,23 +107,39 @@ pub fn run_method(context: &NwipeContext) -> i32 {
         &format!("Starting wipe of device {}", ctx.device_name)
     );
 
-    // Determine which method to use based on the context's prng field
-    let result = match ctx.prng.as_str() {
-        "ops2" => ops2_wipe(&mut ctx),
-        "dod" => dod_wipe(&mut ctx),
-        "gutmann" => gutmann_wipe(&mut ctx),
-        "random" => random_wipe(&mut ctx),
-        "zero" => zero_wipe(&mut ctx),
+    let journal_entry = if no_resume || !resume_supported(&ctx.wipe_method) {
+        None
+    } else {
+        journal::load(&ctx.device_name, &ctx.wipe_method)
+    };
+    let mut resume = Resume::new(journal_entry);
+
+    // Determine which method to use based on the context's wipe_method field
+    let result = match ctx.wipe_method.as_str() {
+        "ops2" => ops2_wipe(&mut ctx, &progress_tx, cancel, &mut resume),
+        "dod" => dod_wipe(&mut ctx, &progress_tx, cancel, &mut resume),
+        "gutmann" => gutmann_wipe(&mut ctx, &progress_tx, cancel, &mut resume),
+        "random" => random_wipe(&mut ctx, &progress_tx, cancel, &mut resume),
+        "zero" => zero_wipe(&mut ctx, &progress_tx, cancel, &mut resume),
+        "encrypted-zero" => encrypted_zero_wipe(&mut ctx, &progress_tx, cancel, &mut resume),
+        "secure-erase" => secure_erase_wipe(&mut ctx, &progress_tx, cancel, &mut resume),
         _ => {
             nwipe_log(
                 LogLevel::Error,
-                &format!("Unknown wipe method: {}", ctx.prng)
+                &format!("Unknown wipe method: {}", ctx.wipe_method)
             );
             -1
         }
     };
 
-    // Record the end time
+    // A successful completion leaves nothing to resume; a failed one keeps
+    // the journal so the next attempt can pick back up where this left off.
+    if result == 0 {
+        journal::clear(&ctx.device_name);
+    }
+
+    // Record the result and end time
+    ctx.result = result;
     ctx.end_time = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap_or_default()
@@ -87,11 +161,92 @@ pub fn run_method(context: &NwipeContext) -> i32 {
         );
     }
 
-    result
+    (result, ctx)
+}
+
+/// Tracks which write/verify call of the running method is in progress, and
+/// where a resumed run should pick back up.
+///
+/// Calls are identified purely by the order the method function issues them
+/// in, which is fixed and identical between runs of the same method with
+/// the same `verify` setting. That's simpler and more robust than keying off
+/// `round_working`/`pass_working`, which a method's final pass sometimes
+/// reuses from an earlier pass purely for progress display.
+pub struct Resume {
+    entry: Option<journal::JournalEntry>,
+    call_index: u32,
+}
+
+/// Whether a wipe method's progress can safely be resumed from an on-disk
+/// journal.
+///
+/// `encrypted-zero`'s entire keystream is determined by the key+nonce pair
+/// `write_encrypted_zero` generates once per run and keeps only in
+/// `context.prng_seed`, in memory; the journal has nowhere to persist that
+/// pair. A "resumed" run would therefore generate a fresh key partway
+/// through the device and be unable to reproduce the keystream already
+/// written under the old, now-lost one, so until the journal can carry the
+/// key+nonce, this method always restarts from scratch instead.
+pub fn resume_supported(method: &str) -> bool {
+    method != "encrypted-zero"
+}
+
+impl Resume {
+    pub fn new(entry: Option<journal::JournalEntry>) -> Self {
+        Self { entry, call_index: 0 }
+    }
+
+    /// Call once, immediately before each write/verify call, in the exact
+    /// order those calls happen. Returns the call's index (to pass through
+    /// for journaling) together with the byte offset it should start from,
+    /// or `None` if the journal shows this exact call already completed in
+    /// a prior run and it should be skipped entirely.
+    fn next(&mut self) -> (u32, Option<u64>) {
+        let index = self.call_index;
+        self.call_index += 1;
+
+        let Some(entry) = &self.entry else { return (index, Some(0)) };
+
+        let offset = match index.cmp(&entry.call_index) {
+            std::cmp::Ordering::Less => None,
+            std::cmp::Ordering::Equal => Some(entry.byte_offset),
+            std::cmp::Ordering::Greater => {
+                // Past the resume point now; every later call runs in full.
+                self.entry = None;
+                Some(0)
+            }
+        };
+
+        (index, offset)
+    }
+}
+
+/// Persist a `JournalEntry` reflecting exactly how far `call_index` has
+/// progressed, so a crash can resume from `byte_offset` instead of
+/// replaying the whole call. Best-effort: a failure to write only costs
+/// resumability, not the wipe itself.
+fn checkpoint(context: &NwipeContext, call_index: u32, byte_offset: u64) {
+    // A call that has reached the end of the device is complete: record it
+    // as the start of the next call, so a resume never re-enters a finished
+    // call at an offset that isn't a multiple of `NWIPE_KNOB_BUFSIZE`.
+    let (call_index, byte_offset) =
+        if byte_offset >= context.device_size { (call_index + 1, 0) } else { (call_index, byte_offset) };
+
+    let entry = journal::JournalEntry {
+        method: context.wipe_method.clone(),
+        call_index,
+        round_working: context.round_working,
+        pass_working: context.pass_working,
+        byte_offset,
+    };
+
+    if let Err(e) = journal::save(&context.device_name, &entry) {
+        nwipe_log(LogLevel::Warning, &format!("{} failed to write progress journal: {}", context.device_name, e));
+    }
 }
 
 /// OPS-II wiping method (DoD 5220.22-M).
-pub fn ops2_wipe(context: &mut NwipeContext) -> i32 {
+pub fn ops2_wipe(context: &mut NwipeContext, progress_tx: &ProgressSink, cancel: &CancelFlag, resume: &mut Resume) -> i32 {
     // Set up the wipe parameters
     context.round_count = 3;
     context.pass_count = 3;
@@ -103,46 +258,60 @@ pub fn ops2_wipe(context: &mut NwipeContext) -> i32 {
         // Pass 1: Write zeros
         context.pass_working = 1;
         context.pass_type = PassType::Write;
-        if let Err(e) = write_pattern(context, &[0x00]) {
-            nwipe_log(
-                LogLevel::Error,
-                &format!("OPS-II write zeros failed: {}", e)
-            );
-            return -1;
+        let (call_index, offset) = resume.next();
+        if let Some(offset) = offset {
+            if let Err(e) = write_pattern(context, progress_tx, &[0x00], cancel, call_index, offset) {
+                nwipe_log(
+                    LogLevel::Error,
+                    &format!("OPS-II write zeros failed: {}", e)
+                );
+                return -1;
+            }
         }
 
         // Pass 2: Write ones
         context.pass_working = 2;
         context.pass_type = PassType::Write;
-        if let Err(e) = write_pattern(context, &[0xFF]) {
-            nwipe_log(
-                LogLevel::Error,
-                &format!("OPS-II write ones failed: {}", e)
-            );
-            return -1;
+        let (call_index, offset) = resume.next();
+        if let Some(offset) = offset {
+            if let Err(e) = write_pattern(context, progress_tx, &[0xFF], cancel, call_index, offset) {
+                nwipe_log(
+                    LogLevel::Error,
+                    &format!("OPS-II write ones failed: {}", e)
+                );
+                return -1;
+            }
         }
 
         // Pass 3: Write random data
         context.pass_working = 3;
         context.pass_type = PassType::Write;
-        if let Err(e) = write_random(context) {
-            nwipe_log(
-                LogLevel::Error,
-                &format!("OPS-II write random failed: {}", e)
-            );
-            return -1;
+        let (call_index, offset) = resume.next();
+        if let Some(offset) = offset {
+            if let Err(e) = write_random(context, progress_tx, cancel, call_index, offset) {
+                nwipe_log(
+                    LogLevel::Error,
+                    &format!("OPS-II write random failed: {}", e)
+                );
+                return -1;
+            }
         }
 
         // Verify if requested
         if context.verify {
             context.pass_working = 4;
             context.pass_type = PassType::Verify;
-            if let Err(e) = verify_random(context) {
-                nwipe_log(
-                    LogLevel::Error,
-                    &format!("OPS-II verify failed: {}", e)
-                );
-                return -1;
+            let (call_index, offset) = resume.next();
+            if let Some(offset) = offset {
+                if let Err(e) = verify_random(context, progress_tx, cancel, call_index, offset) {
+                    nwipe_log_event(
+                        LogLevel::Error,
+                        &format!("OPS-II verify failed: {}", e),
+                        Some(&context.device_name),
+                        Some("verify_fail"),
+                    );
+                    return -1;
+                }
             }
         }
     }
@@ -150,19 +319,22 @@ pub fn ops2_wipe(context: &mut NwipeContext) -> i32 {
     // Final pass: Write zeros
     context.pass_working = context.pass_count;
     context.pass_type = PassType::FinalBlank;
-    if let Err(e) = write_pattern(context, &[0x00]) {
-        nwipe_log(
-            LogLevel::Error,
-            &format!("OPS-II final zero write failed: {}", e)
-        );
-        return -1;
+    let (call_index, offset) = resume.next();
+    if let Some(offset) = offset {
+        if let Err(e) = write_pattern(context, progress_tx, &[0x00], cancel, call_index, offset) {
+            nwipe_log(
+                LogLevel::Error,
+                &format!("OPS-II final zero write failed: {}", e)
+            );
+            return -1;
+        }
     }
 
     0
 }
 
 /// DoD 5220.22-M wiping method.
-pub fn dod_wipe(context: &mut NwipeContext) -> i32 {
+pub fn dod_wipe(context: &mut NwipeContext, progress_tx: &ProgressSink, cancel: &CancelFlag, resume: &mut Resume) -> i32 {
     // Set up the wipe parameters
     context.round_count = 1;
     context.pass_count = 3;
@@ -170,46 +342,60 @@ pub fn dod_wipe(context: &mut NwipeContext) -> i32 {
     // Pass 1: Write zeros
     context.pass_working = 1;
     context.pass_type = PassType::Write;
-    if let Err(e) = write_pattern(context, &[0x00]) {
-        nwipe_log(
-            LogLevel::Error,
-            &format!("DoD write zeros failed: {}", e)
-        );
-        return -1;
+    let (call_index, offset) = resume.next();
+    if let Some(offset) = offset {
+        if let Err(e) = write_pattern(context, progress_tx, &[0x00], cancel, call_index, offset) {
+            nwipe_log(
+                LogLevel::Error,
+                &format!("DoD write zeros failed: {}", e)
+            );
+            return -1;
+        }
     }
 
     // Pass 2: Write ones
     context.pass_working = 2;
     context.pass_type = PassType::Write;
-    if let Err(e) = write_pattern(context, &[0xFF]) {
-        nwipe_log(
-            LogLevel::Error,
-            &format!("DoD write ones failed: {}", e)
-        );
-        return -1;
+    let (call_index, offset) = resume.next();
+    if let Some(offset) = offset {
+        if let Err(e) = write_pattern(context, progress_tx, &[0xFF], cancel, call_index, offset) {
+            nwipe_log(
+                LogLevel::Error,
+                &format!("DoD write ones failed: {}", e)
+            );
+            return -1;
+        }
     }
 
     // Pass 3: Write random data
     context.pass_working = 3;
     context.pass_type = PassType::Write;
-    if let Err(e) = write_random(context) {
-        nwipe_log(
-            LogLevel::Error,
-            &format!("DoD write random failed: {}", e)
-        );
-        return -1;
+    let (call_index, offset) = resume.next();
+    if let Some(offset) = offset {
+        if let Err(e) = write_random(context, progress_tx, cancel, call_index, offset) {
+            nwipe_log(
+                LogLevel::Error,
+                &format!("DoD write random failed: {}", e)
+            );
+            return -1;
+        }
     }
 
     // Verify if requested
     if context.verify {
         context.pass_working = 4;
         context.pass_type = PassType::Verify;
-        if let Err(e) = verify_random(context) {
-            nwipe_log(
-                LogLevel::Error,
-                &format!("DoD verify failed: {}", e)
-            );
-            return -1;
+        let (call_index, offset) = resume.next();
+        if let Some(offset) = offset {
+            if let Err(e) = verify_random(context, progress_tx, cancel, call_index, offset) {
+                nwipe_log_event(
+                    LogLevel::Error,
+                    &format!("DoD verify failed: {}", e),
+                    Some(&context.device_name),
+                    Some("verify_fail"),
+                );
+                return -1;
+            }
         }
     }
 
@@ -217,7 +403,7 @@ pub fn dod_wipe(context: &mut NwipeContext) -> i32 {
 }
 
 /// Gutmann wiping method.
-pub fn gutmann_wipe(context: &mut NwipeContext) -> i32 {
+pub fn gutmann_wipe(context: &mut NwipeContext, progress_tx: &ProgressSink, cancel: &CancelFlag, resume: &mut Resume) -> i32 {
     // Set up the wipe parameters
     context.round_count = 1;
     context.pass_count = 35;
@@ -226,12 +412,15 @@ pub fn gutmann_wipe(context: &mut NwipeContext) -> i32 {
     for pass in 0..4 {
         context.pass_working = pass + 1;
         context.pass_type = PassType::Write;
-        if let Err(e) = write_random(context) {
-            nwipe_log(
-                LogLevel::Error,
-                &format!("Gutmann write random (pass {}) failed: {}", pass + 1, e)
-            );
-            return -1;
+        let (call_index, offset) = resume.next();
+        if let Some(offset) = offset {
+            if let Err(e) = write_random(context, progress_tx, cancel, call_index, offset) {
+                nwipe_log(
+                    LogLevel::Error,
+                    &format!("Gutmann write random (pass {}) failed: {}", pass + 1, e)
+                );
+                return -1;
+            }
         }
     }
 
@@ -269,12 +458,15 @@ pub fn gutmann_wipe(context: &mut NwipeContext) -> i32 {
     for (i, pattern) in patterns.iter().enumerate() {
         context.pass_working = i as i32 + 5;
         context.pass_type = PassType::Write;
-        if let Err(e) = write_pattern(context, *pattern) {
-            nwipe_log(
-                LogLevel::Error,
-                &format!("Gutmann write pattern (pass {}) failed: {}", i + 5, e)
-            );
-            return -1;
+        let (call_index, offset) = resume.next();
+        if let Some(offset) = offset {
+            if let Err(e) = write_pattern(context, progress_tx, *pattern, cancel, call_index, offset) {
+                nwipe_log(
+                    LogLevel::Error,
+                    &format!("Gutmann write pattern (pass {}) failed: {}", i + 5, e)
+                );
+                return -1;
+            }
         }
     }
 
@@ -282,12 +474,15 @@ pub fn gutmann_wipe(context: &mut NwipeContext) -> i32 {
     for pass in 0..4 {
         context.pass_working = pass + 32;
         context.pass_type = PassType::Write;
-        if let Err(e) = write_random(context) {
-            nwipe_log(
-                LogLevel::Error,
-                &format!("Gutmann write random (pass {}) failed: {}", pass + 32, e)
-            );
-            return -1;
+        let (call_index, offset) = resume.next();
+        if let Some(offset) = offset {
+            if let Err(e) = write_random(context, progress_tx, cancel, call_index, offset) {
+                nwipe_log(
+                    LogLevel::Error,
+                    &format!("Gutmann write random (pass {}) failed: {}", pass + 32, e)
+                );
+                return -1;
+            }
         }
     }
 
@@ -295,12 +490,17 @@ pub fn gutmann_wipe(context: &mut NwipeContext) -> i32 {
     if context.verify {
         context.pass_working = 36;
         context.pass_type = PassType::Verify;
-        if let Err(e) = verify_random(context) {
-            nwipe_log(
-                LogLevel::Error,
-                &format!("Gutmann verify failed: {}", e)
-            );
-            return -1;
+        let (call_index, offset) = resume.next();
+        if let Some(offset) = offset {
+            if let Err(e) = verify_random(context, progress_tx, cancel, call_index, offset) {
+                nwipe_log_event(
+                    LogLevel::Error,
+                    &format!("Gutmann verify failed: {}", e),
+                    Some(&context.device_name),
+                    Some("verify_fail"),
+                );
+                return -1;
+            }
         }
     }
 
@@ -308,7 +508,7 @@ pub fn gutmann_wipe(context: &mut NwipeContext) -> i32 {
 }
 
 /// Random data wiping method.
-pub fn random_wipe(context: &mut NwipeContext) -> i32 {
+pub fn random_wipe(context: &mut NwipeContext, progress_tx: &ProgressSink, cancel: &CancelFlag, resume: &mut Resume) -> i32 {
     // Set up the wipe parameters
     context.round_count = 1;
     context.pass_count = 1;
@@ -316,24 +516,32 @@ pub fn random_wipe(context: &mut NwipeContext) -> i32 {
     // Pass 1: Write random data
     context.pass_working = 1;
     context.pass_type = PassType::Write;
-    if let Err(e) = write_random(context) {
-        nwipe_log(
-            LogLevel::Error,
-            &format!("Random write failed: {}", e)
-        );
-        return -1;
+    let (call_index, offset) = resume.next();
+    if let Some(offset) = offset {
+        if let Err(e) = write_random(context, progress_tx, cancel, call_index, offset) {
+            nwipe_log(
+                LogLevel::Error,
+                &format!("Random write failed: {}", e)
+            );
+            return -1;
+        }
     }
 
     // Verify if requested
     if context.verify {
         context.pass_working = 2;
         context.pass_type = PassType::Verify;
-        if let Err(e) = verify_random(context) {
-            nwipe_log(
-                LogLevel::Error,
-                &format!("Random verify failed: {}", e)
-            );
-            return -1;
+        let (call_index, offset) = resume.next();
+        if let Some(offset) = offset {
+            if let Err(e) = verify_random(context, progress_tx, cancel, call_index, offset) {
+                nwipe_log_event(
+                    LogLevel::Error,
+                    &format!("Random verify failed: {}", e),
+                    Some(&context.device_name),
+                    Some("verify_fail"),
+                );
+                return -1;
+            }
         }
     }
 
@@ -341,7 +549,7 @@ pub fn random_wipe(context: &mut NwipeContext) -> i32 {
 }
 
 /// Zero fill wiping method.
-pub fn zero_wipe(context: &mut NwipeContext) -> i32 {
+pub fn zero_wipe(context: &mut NwipeContext, progress_tx: &ProgressSink, cancel: &CancelFlag, resume: &mut Resume) -> i32 {
     // Set up the wipe parameters
     context.round_count = 1;
     context.pass_count = 1;
@@ -349,37 +557,411 @@ pub fn zero_wipe(context: &mut NwipeContext) -> i32 {
     // Pass 1: Write zeros
     context.pass_working = 1;
     context.pass_type = PassType::Write;
-    if let Err(e) = write_pattern(context, &[0x00]) {
-        nwipe_log(
-            LogLevel::Error,
-            &format!("Zero write failed: {}", e)
-        );
-        return -1;
+    let (call_index, offset) = resume.next();
+    if let Some(offset) = offset {
+        if let Err(e) = write_pattern(context, progress_tx, &[0x00], cancel, call_index, offset) {
+            nwipe_log(
+                LogLevel::Error,
+                &format!("Zero write failed: {}", e)
+            );
+            return -1;
+        }
     }
 
     // Verify if requested
     if context.verify {
         context.pass_working = 2;
         context.pass_type = PassType::Verify;
-        if let Err(e) = verify_pattern(context, &[0x00]) {
+        let (call_index, offset) = resume.next();
+        if let Some(offset) = offset {
+            if let Err(e) = verify_pattern(context, progress_tx, &[0x00], cancel, call_index, offset) {
+                nwipe_log_event(
+                    LogLevel::Error,
+                    &format!("Zero verify failed: {}", e),
+                    Some(&context.device_name),
+                    Some("verify_fail"),
+                );
+                return -1;
+            }
+        }
+    }
+
+    0
+}
+
+/// Encrypted-zero wiping method.
+///
+/// Fills the device with pseudo-random ciphertext at close to memcpy speed
+/// rather than paying a general-purpose CSPRNG's per-byte cost: a random
+/// 256-bit key and nonce are generated once, and each block's keystream is a
+/// pure function of (key, nonce, absolute byte offset), generated via
+/// ChaCha20 in its native counter-mode form. Verification regenerates and
+/// compares each block directly from that same function rather than
+/// replaying the whole stream from the start, exactly like `write_random`'s
+/// seekable design. The security goal is to leave the platter looking like a
+/// freshly-encrypted volume, not to perform a multi-pass overwrite; use
+/// `ops2`/`dod`/`gutmann` where repeated overwriting is required.
+pub fn encrypted_zero_wipe(context: &mut NwipeContext, progress_tx: &ProgressSink, cancel: &CancelFlag, resume: &mut Resume) -> i32 {
+    // Set up the wipe parameters
+    context.round_count = 1;
+    context.pass_count = 1;
+
+    // Pass 1: Write encrypted-zero ciphertext
+    context.pass_working = 1;
+    context.pass_type = PassType::Write;
+    let (call_index, offset) = resume.next();
+    if let Some(offset) = offset {
+        if let Err(e) = write_encrypted_zero(context, progress_tx, cancel, call_index, offset) {
             nwipe_log(
                 LogLevel::Error,
-                &format!("Zero verify failed: {}", e)
+                &format!("Encrypted-zero write failed: {}", e)
             );
+            zeroize_seed(&mut context.prng_seed);
             return -1;
         }
     }
 
-    0
+    let mut result = 0;
+
+    // Verify if requested
+    if context.verify {
+        context.pass_working = 2;
+        context.pass_type = PassType::Verify;
+        let (call_index, offset) = resume.next();
+        if let Some(offset) = offset {
+            if let Err(e) = verify_encrypted_zero(context, progress_tx, cancel, call_index, offset) {
+                nwipe_log_event(
+                    LogLevel::Error,
+                    &format!("Encrypted-zero verify failed: {}", e),
+                    Some(&context.device_name),
+                    Some("verify_fail"),
+                );
+                result = -1;
+            }
+        }
+    }
+
+    // The key/nonce are only needed for verification; once this pass is
+    // done (verified or not), there's no reason to keep them around.
+    zeroize_seed(&mut context.prng_seed);
+
+    result
 }
 
-/// Write a pattern to the device.
-fn write_pattern(context: &mut NwipeContext, pattern: &[u8]) -> Result<(), io::Error> {
+/// Hardware-assisted erase for SSDs/NVMe devices.
+///
+/// Tries ATA SECURITY ERASE UNIT, NVMe Sanitize, or BLKDISCARD/BLKSECDISCARD
+/// depending on what the device probe found. Falls back to the `ops2`
+/// software overwrite when the device has no hardware erase support.
+pub fn secure_erase_wipe(context: &mut NwipeContext, progress_tx: &ProgressSink, cancel: &CancelFlag, resume: &mut Resume) -> i32 {
+    match erase::hardware_erase(context) {
+        Ok(true) => 0,
+        Ok(false) => {
+            nwipe_log(
+                LogLevel::Notice,
+                &format!("{} no hardware erase available, falling back to OPS-II overwrite", context.device_name),
+            );
+            ops2_wipe(context, progress_tx, cancel, resume)
+        }
+        Err(e) => {
+            nwipe_log(LogLevel::Error, &format!("Hardware erase of {} failed: {}", context.device_name, e));
+            -1
+        }
+    }
+}
+
+/// A page-aligned buffer, required by `O_DIRECT` writes: both the buffer's
+/// address and the write length must be aligned to the device's logical
+/// block size. 4 KiB covers every logical block size nwipe is likely to
+/// encounter in practice, and `NWIPE_KNOB_BUFSIZE` (4 MiB) is always a
+/// multiple of it, so only the last, possibly short, block of a device
+/// needs a buffer smaller than a full `NWIPE_KNOB_BUFSIZE`.
+struct AlignedBuffer {
+    ptr: *mut u8,
+    len: usize,
+    layout: std::alloc::Layout,
+}
+
+// SAFETY: the allocation is exclusively owned by this `AlignedBuffer` and,
+// once handed to the writer thread via `Arc`, is only ever read from (never
+// mutated), so sharing and moving it across the channel is sound.
+unsafe impl Send for AlignedBuffer {}
+unsafe impl Sync for AlignedBuffer {}
+
+impl AlignedBuffer {
+    const ALIGNMENT: usize = 4096;
+
+    fn new(len: usize) -> Self {
+        let layout = std::alloc::Layout::from_size_align(len.max(1), Self::ALIGNMENT)
+            .expect("NWIPE_KNOB_BUFSIZE-sized O_DIRECT buffers always have a valid layout");
+        let ptr = unsafe { std::alloc::alloc_zeroed(layout) };
+        assert!(!ptr.is_null(), "failed to allocate an O_DIRECT write buffer");
+        Self { ptr, len, layout }
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.ptr, self.len) }
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [u8] {
+        unsafe { std::slice::from_raw_parts_mut(self.ptr, self.len) }
+    }
+}
+
+impl Drop for AlignedBuffer {
+    fn drop(&mut self) {
+        unsafe { std::alloc::dealloc(self.ptr, self.layout) };
+    }
+}
+
+/// A background thread that writes submitted buffers to `context.device_fd`
+/// through `O_DIRECT`, so the caller can generate the next buffer while the
+/// previous one is still being flushed to the device instead of waiting on
+/// it, and so gigabytes of wipe data never evict useful pages from the page
+/// cache.
+///
+/// `O_DIRECT` is set on `context.device_fd` itself (the flag applies to the
+/// open file description, not a particular fd), so this is only ever used
+/// for the duration of a single write call and `finish` always restores the
+/// fd's original flags before returning, leaving buffered reads (e.g. a
+/// subsequent verify pass) unaffected.
+struct DirectWriter {
+    fd: RawFd,
+    original_flags: OFlag,
+    tx: Option<SyncSender<Arc<AlignedBuffer>>>,
+    completion_rx: Receiver<Result<u64, io::Error>>,
+    handle: Option<thread::JoinHandle<()>>,
+}
+
+impl DirectWriter {
+    /// Try to enable `O_DIRECT` on `context.device_fd` and start a writer
+    /// thread seeked to `start_offset`. Returns `None` if `O_DIRECT` isn't
+    /// supported on this device/filesystem, leaving the fd's flags
+    /// untouched, so the caller can fall back to a plain buffered loop.
+    fn open(context: &NwipeContext, start_offset: u64) -> Option<Self> {
+        let fd = context.device_fd;
+
+        let original_flags = OFlag::from_bits_truncate(fcntl(fd, FcntlArg::F_GETFL).ok()?);
+        let mut direct_flags = original_flags;
+        direct_flags.insert(OFlag::O_DIRECT);
+        fcntl(fd, FcntlArg::F_SETFL(direct_flags)).ok()?;
+
+        let mut file = unsafe { File::from_raw_fd(fd) };
+        if file.seek(SeekFrom::Start(start_offset)).is_err() {
+            std::mem::forget(file);
+            let _ = fcntl(fd, FcntlArg::F_SETFL(original_flags));
+            return None;
+        }
+
+        // Capacity 1: the sender can hand off the next buffer while the
+        // writer thread is still flushing the previous one, which is the
+        // whole point of the pipeline, without letting generation run
+        // arbitrarily far ahead of the device.
+        let (tx, rx) = mpsc::sync_channel::<Arc<AlignedBuffer>>(1);
+        let (completion_tx, completion_rx) = mpsc::channel();
+
+        let handle = thread::spawn(move || {
+            for buf in rx {
+                let result = file.write_all(buf.as_slice()).map(|_| buf.as_slice().len() as u64);
+                let failed = result.is_err();
+                let _ = completion_tx.send(result);
+                if failed {
+                    break;
+                }
+            }
+
+            // `context.device_fd` owns this fd for the rest of the wipe;
+            // don't let `file`'s drop close it out from under it.
+            std::mem::forget(file);
+        });
+
+        Some(Self { fd, original_flags, tx: Some(tx), completion_rx, handle: Some(handle) })
+    }
+
+    /// Hand a buffer to the writer thread. Blocks only if the writer hasn't
+    /// finished the previous buffer yet.
+    fn submit(&self, buf: Arc<AlignedBuffer>) -> Result<(), io::Error> {
+        self.tx
+            .as_ref()
+            .expect("submit called after finish")
+            .send(buf)
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "O_DIRECT writer thread exited early"))
+    }
+
+    /// Drain one completed-write notification without blocking, if one is
+    /// available yet, so progress/the journal can advance as writes actually
+    /// land rather than only once every buffer has been generated.
+    fn try_recv_progress(&self) -> Option<Result<u64, io::Error>> {
+        self.completion_rx.try_recv().ok()
+    }
+
+    /// Close the channel, wait for every already-submitted buffer to finish
+    /// writing (reporting each one's size through `on_complete` as it
+    /// lands), restore the fd's original flags, and return the first error
+    /// encountered, if any.
+    fn finish(mut self, mut on_complete: impl FnMut(u64)) -> Result<(), io::Error> {
+        // Dropping the sender closes the channel, which ends the writer
+        // thread's `for buf in rx` loop once it's drained.
+        self.tx.take();
+
+        let mut result = Ok(());
+        while let Ok(write_result) = self.completion_rx.recv() {
+            match write_result {
+                Ok(n) => on_complete(n),
+                Err(e) => result = Err(e),
+            }
+        }
+
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+
+        let _ = fcntl(self.fd, FcntlArg::F_SETFL(self.original_flags));
+
+        result
+    }
+}
+
+/// Apply one confirmed write of `delta` bytes, reaching `offset_after` (this
+/// call's absolute byte offset into the device after the write), to
+/// progress accounting: advance `bytes_written`/`bytes_total`, recompute
+/// `round_percent` from `offset_after` (not from `bytes_written`, which also
+/// accumulates every other pass/round of the method and so isn't this
+/// call's own fraction-of-device-complete), checkpoint the journal, and
+/// report progress. Shared by `write_pipelined`'s periodic drain and its
+/// final drain in `DirectWriter::finish`, so both paths advance progress
+/// identically.
+fn record_write_progress(context: &mut NwipeContext, progress_tx: &ProgressSink, call_index: u32, offset_after: u64, delta: u64) {
+    context.bytes_written += delta;
+    context.bytes_total += delta;
+    context.round_percent = offset_after as f64 / context.device_size as f64 * 100.0;
+    checkpoint(context, call_index, offset_after.min(context.device_size));
+    update_eta_throughput(context);
+    report_progress(progress_tx, context);
+}
+
+/// Drive `writer` over every block from `start_offset` to the end of the
+/// device, calling `fill` to produce each block's bytes into a fresh aligned
+/// buffer just before handing it off. Used by `write_pattern` and
+/// `write_random`, which differ only in how a block's bytes are produced.
+///
+/// Progress/the journal only advance on confirmed writes reported back by
+/// the writer thread (via `record_write_progress`), not on generation, so
+/// they always reflect bytes actually on the device rather than merely
+/// queued.
+fn write_pipelined(
+    context: &mut NwipeContext,
+    progress_tx: &ProgressSink,
+    cancel: &CancelFlag,
+    call_index: u32,
+    start_offset: u64,
+    writer: DirectWriter,
+    mut fill: impl FnMut(&mut [u8]),
+) -> Result<(), io::Error> {
+    let block_count = (context.device_size + NWIPE_KNOB_BUFSIZE as u64 - 1) / NWIPE_KNOB_BUFSIZE as u64;
+    let first_block = start_offset / NWIPE_KNOB_BUFSIZE as u64;
+
+    let mut result: Result<(), io::Error> = Ok(());
+    let mut offset = start_offset;
+
+    for block in first_block..block_count {
+        if cancel.is_set() {
+            result = Err(io::Error::new(io::ErrorKind::Interrupted, "Wipe interrupted by user"));
+            break;
+        }
+
+        let size = if block == block_count - 1 && context.device_size % NWIPE_KNOB_BUFSIZE as u64 != 0 {
+            (context.device_size % NWIPE_KNOB_BUFSIZE as u64) as usize
+        } else {
+            NWIPE_KNOB_BUFSIZE
+        };
+
+        let mut aligned = AlignedBuffer::new(size);
+        fill(aligned.as_mut_slice());
+
+        if let Err(e) = writer.submit(Arc::new(aligned)) {
+            result = Err(e);
+            break;
+        }
+
+        while let Some(completed) = writer.try_recv_progress() {
+            match completed {
+                Ok(n) => {
+                    offset += n;
+                    record_write_progress(context, progress_tx, call_index, offset, n);
+                }
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
+            }
+        }
+        if result.is_err() {
+            break;
+        }
+    }
+
+    // Whether the loop above ended cleanly, on cancellation, or on an
+    // error, wait for every already-submitted buffer to finish landing (or
+    // failing) and credit its bytes, then restore buffered I/O on the fd.
+    let finish_result = writer.finish(|n| {
+        offset += n;
+        record_write_progress(context, progress_tx, call_index, offset, n);
+    });
+
+    result.and(finish_result)
+}
+
+/// Write a pattern to the device, starting from `start_offset` bytes in
+/// (0 for a fresh pass, or a journaled offset when resuming one). Writes up
+/// to the end of the device; `start_offset` must be a multiple of
+/// `NWIPE_KNOB_BUFSIZE`, which every value this is called with always is, as
+/// either `0` or a prior `checkpoint()` of this same `call_index`.
+fn write_pattern(
+    context: &mut NwipeContext,
+    progress_tx: &ProgressSink,
+    pattern: &[u8],
+    cancel: &CancelFlag,
+    call_index: u32,
+    start_offset: u64,
+) -> Result<(), io::Error> {
+    // An all-zero pattern can often be offloaded to the device itself via
+    // BLKZEROOUT, which is typically far faster than a buffered write loop
+    // (e.g. WRITE SAME under the hood on SCSI/SATA, or a metadata-only
+    // operation on thinly-provisioned media). Try that first and only fall
+    // back to the buffered loop below if the kernel/device doesn't support it.
+    if pattern.iter().all(|&b| b == 0) {
+        match blank_device(context, progress_tx, cancel, call_index, start_offset) {
+            Ok(true) => return Ok(()),
+            Ok(false) => {
+                nwipe_log(
+                    LogLevel::Info,
+                    &format!("{} BLKZEROOUT not supported, falling back to buffered zero write", context.device_name),
+                );
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    // Double-buffer pattern generation against the write itself through a
+    // background O_DIRECT writer, so gigabytes of wipe data don't evict
+    // useful pages from the cache and the next block is filled in while the
+    // previous one is still landing on the device. Falls back to the plain
+    // buffered loop below when O_DIRECT can't be enabled here.
+    if let Some(writer) = DirectWriter::open(context, start_offset) {
+        let pattern = pattern.to_vec();
+        return write_pipelined(context, progress_tx, cancel, call_index, start_offset, writer, move |dest| {
+            for (i, byte) in dest.iter_mut().enumerate() {
+                *byte = pattern[i % pattern.len()];
+            }
+        });
+    }
+
     // Open the device
     let mut file = unsafe { File::from_raw_fd(context.device_fd) };
 
-    // Seek to the beginning of the device
-    file.seek(SeekFrom::Start(0))?;
+    // Seek to where this call starts from
+    file.seek(SeekFrom::Start(start_offset))?;
 
     // Create a buffer filled with the pattern
     let mut buffer = vec![0u8; NWIPE_KNOB_BUFSIZE];
@@ -389,11 +971,12 @@ fn write_pattern(context: &mut NwipeContext, pattern: &[u8]) -> Result<(), io::E
 
     // Calculate the number of blocks to write
     let block_count = (context.device_size + NWIPE_KNOB_BUFSIZE as u64 - 1) / NWIPE_KNOB_BUFSIZE as u64;
+    let first_block = start_offset / NWIPE_KNOB_BUFSIZE as u64;
 
     // Write the pattern to the device
-    for block in 0..block_count {
+    for block in first_block..block_count {
         // Check if we should abort
-        if unsafe { crate::TERMINATE_SIGNAL } {
+        if cancel.is_set() {
             return Err(io::Error::new(io::ErrorKind::Interrupted, "Wipe interrupted by user"));
         }
 
@@ -411,9 +994,11 @@ fn write_pattern(context: &mut NwipeContext, pattern: &[u8]) -> Result<(), io::E
         context.bytes_written += size as u64;
         context.bytes_total += size as u64;
         context.round_percent = (block as f64 + 1.0) / block_count as f64 * 100.0;
+        checkpoint(context, call_index, ((block + 1) * NWIPE_KNOB_BUFSIZE as u64).min(context.device_size));
 
         // Update ETA and throughput
         update_eta_throughput(context);
+        report_progress(progress_tx, context);
     }
 
     // Sync the device
@@ -427,27 +1012,110 @@ fn write_pattern(context: &mut NwipeContext, pattern: &[u8]) -> Result<(), io::E
     Ok(())
 }
 
-/// Write random data to the device.
-fn write_random(context: &mut NwipeContext) -> Result<(), io::Error> {
+/// Zero the device from `start_offset` onwards using `BLKZEROOUT`, issued in
+/// `BLKZEROOUT_CHUNK`-sized ranges so progress/ETA keep advancing and
+/// `cancel` is still honoured on large devices.
+///
+/// Returns `Ok(true)` if the remainder of the device was fully zeroed this
+/// way, `Ok(false)` if the very first ioctl call reports
+/// `ENOTSUP`/`EOPNOTSUPP` (so the caller should fall back to the buffered
+/// write loop; nothing has been written yet), or `Err` for any other failure.
+fn blank_device(
+    context: &mut NwipeContext,
+    progress_tx: &ProgressSink,
+    cancel: &CancelFlag,
+    call_index: u32,
+    start_offset: u64,
+) -> Result<bool, io::Error> {
+    let mut offset = start_offset;
+
+    while offset < context.device_size {
+        if cancel.is_set() {
+            return Err(io::Error::new(io::ErrorKind::Interrupted, "Wipe interrupted by user"));
+        }
+
+        let length = BLKZEROOUT_CHUNK.min(context.device_size - offset);
+        let range: [u64; 2] = [offset, length];
+
+        let result = unsafe { libc::ioctl(context.device_fd, BLKZEROOUT, range.as_ptr()) };
+        if result != 0 {
+            let err = io::Error::last_os_error();
+            // ENOTSUP and EOPNOTSUPP are the same errno value on Linux.
+            if offset == start_offset && err.raw_os_error() == Some(libc::EOPNOTSUPP) {
+                return Ok(false);
+            }
+            return Err(err);
+        }
+
+        offset += length;
+
+        // Update progress
+        context.bytes_written += length;
+        context.bytes_total += length;
+        context.round_percent = offset as f64 / context.device_size as f64 * 100.0;
+        checkpoint(context, call_index, offset);
+
+        // Update ETA and throughput
+        update_eta_throughput(context);
+        report_progress(progress_tx, context);
+    }
+
+    nwipe_log(LogLevel::Notice, &format!("{} zeroed via BLKZEROOUT", context.device_name));
+
+    Ok(true)
+}
+
+/// Write random data to the device, starting from `start_offset` bytes in.
+/// See `write_pattern` for the resume contract `start_offset` follows.
+fn write_random(
+    context: &mut NwipeContext,
+    progress_tx: &ProgressSink,
+    cancel: &CancelFlag,
+    call_index: u32,
+    start_offset: u64,
+) -> Result<(), io::Error> {
+    // Initialize the PRNG. Reuse the context's seed if one is already
+    // recorded (e.g. a previous pass on this context), otherwise a fresh
+    // seed is generated and stored back so a later verify pass can
+    // re-instantiate the identical PRNG and regenerate this stream.
+    let prior_seed = Some(&context.prng_seed).filter(|s| s.length > 0);
+    let (mut prng, seed_used) = prng::init_prng(&context.prng, prior_seed)?;
+    context.prng_seed = seed_used;
+
+    // Resuming partway through: advance the stream to match, so the bytes
+    // generated from here on pick up exactly where the interrupted run left
+    // off instead of restarting the keystream from block zero.
+    if start_offset > 0 {
+        prng.seek_to_byte(start_offset);
+    }
+
+    // As in `write_pattern`, prefer overlapping generation with I/O through
+    // a background O_DIRECT writer, falling back to the buffered loop below
+    // if O_DIRECT can't be enabled here. `prng` is only moved into the
+    // pipelined path, so it's still available below if this isn't taken.
+    if let Some(writer) = DirectWriter::open(context, start_offset) {
+        return write_pipelined(context, progress_tx, cancel, call_index, start_offset, writer, move |dest| {
+            prng.fill_bytes(dest);
+        });
+    }
+
     // Open the device
     let mut file = unsafe { File::from_raw_fd(context.device_fd) };
 
-    // Seek to the beginning of the device
-    file.seek(SeekFrom::Start(0))?;
+    // Seek to where this call starts from
+    file.seek(SeekFrom::Start(start_offset))?;
 
     // Create a buffer for random data
     let mut buffer = vec![0u8; NWIPE_KNOB_BUFSIZE];
 
-    // Initialize the PRNG
-    let mut prng = prng::init_prng(&context.prng)?;
-
     // Calculate the number of blocks to write
     let block_count = (context.device_size + NWIPE_KNOB_BUFSIZE as u64 - 1) / NWIPE_KNOB_BUFSIZE as u64;
+    let first_block = start_offset / NWIPE_KNOB_BUFSIZE as u64;
 
     // Write random data to the device
-    for block in 0..block_count {
+    for block in first_block..block_count {
         // Check if we should abort
-        if unsafe { crate::TERMINATE_SIGNAL } {
+        if cancel.is_set() {
             return Err(io::Error::new(io::ErrorKind::Interrupted, "Wipe interrupted by user"));
         }
 
@@ -468,9 +1136,11 @@ fn write_random(context: &mut NwipeContext) -> Result<(), io::Error> {
         context.bytes_written += size as u64;
         context.bytes_total += size as u64;
         context.round_percent = (block as f64 + 1.0) / block_count as f64 * 100.0;
+        checkpoint(context, call_index, ((block + 1) * NWIPE_KNOB_BUFSIZE as u64).min(context.device_size));
 
         // Update ETA and throughput
         update_eta_throughput(context);
+        report_progress(progress_tx, context);
     }
 
     // Sync the device
@@ -484,13 +1154,22 @@ fn write_random(context: &mut NwipeContext) -> Result<(), io::Error> {
     Ok(())
 }
 
-/// Verify that a pattern was written correctly.
-fn verify_pattern(context: &mut NwipeContext, pattern: &[u8]) -> Result<(), io::Error> {
+/// Verify that a pattern was written correctly, checking from `start_offset`
+/// bytes in. The preceding bytes are assumed already verified by an earlier,
+/// now-journaled-past, run of this same call.
+fn verify_pattern(
+    context: &mut NwipeContext,
+    progress_tx: &ProgressSink,
+    pattern: &[u8],
+    cancel: &CancelFlag,
+    call_index: u32,
+    start_offset: u64,
+) -> Result<(), io::Error> {
     // Open the device
     let mut file = unsafe { File::from_raw_fd(context.device_fd) };
 
-    // Seek to the beginning of the device
-    file.seek(SeekFrom::Start(0))?;
+    // Seek to where this call starts from
+    file.seek(SeekFrom::Start(start_offset))?;
 
     // Create a buffer for reading
     let mut buffer = vec![0u8; NWIPE_KNOB_BUFSIZE];
@@ -503,11 +1182,12 @@ fn verify_pattern(context: &mut NwipeContext, pattern: &[u8]) -> Result<(), io::
 
     // Calculate the number of blocks to read
     let block_count = (context.device_size + NWIPE_KNOB_BUFSIZE as u64 - 1) / NWIPE_KNOB_BUFSIZE as u64;
+    let first_block = start_offset / NWIPE_KNOB_BUFSIZE as u64;
 
     // Read and verify the device
-    for block in 0..block_count {
+    for block in first_block..block_count {
         // Check if we should abort
-        if unsafe { crate::TERMINATE_SIGNAL } {
+        if cancel.is_set() {
             return Err(io::Error::new(io::ErrorKind::Interrupted, "Verification interrupted by user"));
         }
 
@@ -540,9 +1220,11 @@ fn verify_pattern(context: &mut NwipeContext, pattern: &[u8]) -> Result<(), io::
         context.bytes_verified += size as u64;
         context.bytes_total += size as u64;
         context.round_percent = (block as f64 + 1.0) / block_count as f64 * 100.0;
+        checkpoint(context, call_index, ((block + 1) * NWIPE_KNOB_BUFSIZE as u64).min(context.device_size));
 
         // Update ETA and throughput
         update_eta_throughput(context);
+        report_progress(progress_tx, context);
     }
 
     // Don't close the file descriptor as it's owned by the context
@@ -551,28 +1233,44 @@ fn verify_pattern(context: &mut NwipeContext, pattern: &[u8]) -> Result<(), io::
     Ok(())
 }
 
-/// Verify that random data was written correctly.
-fn verify_random(context: &mut NwipeContext) -> Result<(), io::Error> {
-    // This is a placeholder for random data verification
-    // In a real implementation, we would need to store the random data or its hash
-    // for verification, but for now we'll just simulate verification
-
+/// Verify that random data was written correctly, checking from
+/// `start_offset` bytes in. See `write_random`/`verify_pattern` for how
+/// resuming partway through is handled.
+fn verify_random(
+    context: &mut NwipeContext,
+    progress_tx: &ProgressSink,
+    cancel: &CancelFlag,
+    call_index: u32,
+    start_offset: u64,
+) -> Result<(), io::Error> {
     // Open the device
     let mut file = unsafe { File::from_raw_fd(context.device_fd) };
 
-    // Seek to the beginning of the device
-    file.seek(SeekFrom::Start(0))?;
+    // Seek to where this call starts from
+    file.seek(SeekFrom::Start(start_offset))?;
 
-    // Create a buffer for reading
+    // Create a buffer for reading, and a matching buffer for the PRNG
+    // output regenerated from the seed the write pass recorded.
     let mut buffer = vec![0u8; NWIPE_KNOB_BUFSIZE];
+    let mut expected = vec![0u8; NWIPE_KNOB_BUFSIZE];
+
+    // Re-instantiate the PRNG from the seed `write_random` recorded, so the
+    // exact same byte stream can be regenerated on the fly for comparison.
+    let recorded_seed = Some(&context.prng_seed).filter(|s| s.length > 0);
+    let (mut prng, _) = prng::init_prng(&context.prng, recorded_seed)?;
+
+    if start_offset > 0 {
+        prng.seek_to_byte(start_offset);
+    }
 
     // Calculate the number of blocks to read
     let block_count = (context.device_size + NWIPE_KNOB_BUFSIZE as u64 - 1) / NWIPE_KNOB_BUFSIZE as u64;
+    let first_block = start_offset / NWIPE_KNOB_BUFSIZE as u64;
 
-    // Read and "verify" the device
-    for block in 0..block_count {
+    // Read and verify the device
+    for block in first_block..block_count {
         // Check if we should abort
-        if unsafe { crate::TERMINATE_SIGNAL } {
+        if cancel.is_set() {
             return Err(io::Error::new(io::ErrorKind::Interrupted, "Verification interrupted by user"));
         }
 
@@ -586,31 +1284,225 @@ fn verify_random(context: &mut NwipeContext) -> Result<(), io::Error> {
         // Read the block
         file.read_exact(&mut buffer[0..size])?;
 
-        // In a real implementation, we would verify the block against the expected random data
-        // For now, we just check that the data is not all zeros or all ones
-        let mut all_zeros = true;
-        let mut all_ones = true;
+        // Regenerate the expected bytes from the recorded seed
+        prng.fill_bytes(&mut expected[0..size]);
 
-        for i in 0..size {
-            if buffer[i] != 0 {
-                all_zeros = false;
-            }
-            if buffer[i] != 0xFF {
-                all_ones = false;
-            }
+        // Verify the block, reporting the first mismatching byte's absolute
+        // device offset rather than just the block number, so a failure can
+        // be pinpointed the same way `verify_pattern` does.
+        if let Some(i) = (0..size).find(|&i| buffer[i] != expected[i]) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "Verification failed at offset {}: expected {:#04x} (regenerated from the recorded PRNG seed), found {:#04x}",
+                    block * NWIPE_KNOB_BUFSIZE as u64 + i as u64,
+                    expected[i],
+                    buffer[i]
+                )
+            ));
+        }
 
-            if !all_zeros && !all_ones {
-                break;
-            }
+        // Update progress
+        context.bytes_verified += size as u64;
+        context.bytes_total += size as u64;
+        context.round_percent = (block as f64 + 1.0) / block_count as f64 * 100.0;
+        checkpoint(context, call_index, ((block + 1) * NWIPE_KNOB_BUFSIZE as u64).min(context.device_size));
+
+        // Update ETA and throughput
+        update_eta_throughput(context);
+        report_progress(progress_tx, context);
+    }
+
+    // Don't close the file descriptor as it's owned by the context
+    std::mem::forget(file);
+
+    Ok(())
+}
+
+// `encrypted_zero_wipe`'s key+nonce, packed into a `PrngSeed` the same way
+// `write_random` packs its seed: key first, then nonce.
+const ENCRYPTED_ZERO_KEY_LEN: usize = 32;
+const ENCRYPTED_ZERO_NONCE_LEN: usize = 8;
+const ENCRYPTED_ZERO_SEED_LEN: usize = ENCRYPTED_ZERO_KEY_LEN + ENCRYPTED_ZERO_NONCE_LEN;
+
+/// Generate a fresh random key+nonce for `encrypted_zero_wipe`.
+fn new_encrypted_zero_seed() -> Result<PrngSeed, io::Error> {
+    let mut bytes = vec![0u8; ENCRYPTED_ZERO_SEED_LEN];
+    getrandom::getrandom(&mut bytes)
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("Failed to get random seed: {}", e)))?;
+    Ok(PrngSeed { length: bytes.len(), s: bytes })
+}
+
+/// Build the ChaCha20 keystream generator `encrypted_zero_wipe`'s write and
+/// verify passes both use, keyed by `seed`'s key+nonce. `fill_block` can jump
+/// straight to any block's keystream via its absolute byte offset, since the
+/// keystream is addressed by ChaCha20's word-position counter rather than a
+/// serial stream.
+struct EncryptedZeroKeystream {
+    rng: ChaCha20Rng,
+}
+
+impl EncryptedZeroKeystream {
+    fn new(seed: &PrngSeed) -> Self {
+        let mut key = [0u8; ENCRYPTED_ZERO_KEY_LEN];
+        key.copy_from_slice(&seed.s[0..ENCRYPTED_ZERO_KEY_LEN]);
+        let mut nonce_bytes = [0u8; ENCRYPTED_ZERO_NONCE_LEN];
+        nonce_bytes.copy_from_slice(&seed.s[ENCRYPTED_ZERO_KEY_LEN..ENCRYPTED_ZERO_SEED_LEN]);
+
+        let mut rng = ChaCha20Rng::from_seed(key);
+        rng.set_stream(u64::from_le_bytes(nonce_bytes));
+
+        Self { rng }
+    }
+
+    /// Fill `dest` with the keystream for the block starting at `offset`.
+    fn fill_block(&mut self, offset: u64, dest: &mut [u8]) {
+        // Word position counts 4-byte words; every offset this is called
+        // with is block-aligned, so this division is always exact.
+        self.rng.set_word_pos((offset / 4) as u128);
+        self.rng.fill_bytes(dest);
+    }
+}
+
+/// Best-effort overwrite of a `PrngSeed`'s bytes, so an `encrypted-zero`
+/// pass's key+nonce don't linger in memory once verification is done with
+/// them. Uses volatile writes so the compiler can't optimize the loop away,
+/// though without a hardware guarantee against e.g. register/cache spills.
+fn zeroize_seed(seed: &mut PrngSeed) {
+    for byte in seed.s.iter_mut() {
+        unsafe { std::ptr::write_volatile(byte, 0) };
+    }
+}
+
+/// Write `encrypted_zero_wipe`'s pseudo-random ciphertext to the device,
+/// starting from `start_offset` bytes in. See `write_pattern` for the resume
+/// contract `start_offset` follows.
+fn write_encrypted_zero(
+    context: &mut NwipeContext,
+    progress_tx: &ProgressSink,
+    cancel: &CancelFlag,
+    call_index: u32,
+    start_offset: u64,
+) -> Result<(), io::Error> {
+    // Open the device
+    let mut file = unsafe { File::from_raw_fd(context.device_fd) };
+
+    // Seek to where this call starts from
+    file.seek(SeekFrom::Start(start_offset))?;
+
+    // Reuse a key+nonce already held in the context if it has the right
+    // length, otherwise generate a fresh pair and record it so the verify
+    // pass below can regenerate the identical keystream.
+    if context.prng_seed.length != ENCRYPTED_ZERO_SEED_LEN {
+        context.prng_seed = new_encrypted_zero_seed()?;
+    }
+    let mut keystream = EncryptedZeroKeystream::new(&context.prng_seed);
+
+    let mut buffer = vec![0u8; NWIPE_KNOB_BUFSIZE];
+
+    // Calculate the number of blocks to write
+    let block_count = (context.device_size + NWIPE_KNOB_BUFSIZE as u64 - 1) / NWIPE_KNOB_BUFSIZE as u64;
+    let first_block = start_offset / NWIPE_KNOB_BUFSIZE as u64;
+
+    for block in first_block..block_count {
+        // Check if we should abort
+        if cancel.is_set() {
+            return Err(io::Error::new(io::ErrorKind::Interrupted, "Wipe interrupted by user"));
+        }
+
+        // Calculate the size of this block
+        let size = if block == block_count - 1 && context.device_size % NWIPE_KNOB_BUFSIZE as u64 != 0 {
+            (context.device_size % NWIPE_KNOB_BUFSIZE as u64) as usize
+        } else {
+            NWIPE_KNOB_BUFSIZE
+        };
+
+        // Fill the buffer with this block's keystream
+        let block_offset = block * NWIPE_KNOB_BUFSIZE as u64;
+        keystream.fill_block(block_offset, &mut buffer[0..size]);
+
+        // Write the block
+        file.write_all(&buffer[0..size])?;
+
+        // Update progress
+        context.bytes_written += size as u64;
+        context.bytes_total += size as u64;
+        context.round_percent = (block as f64 + 1.0) / block_count as f64 * 100.0;
+        checkpoint(context, call_index, ((block + 1) * NWIPE_KNOB_BUFSIZE as u64).min(context.device_size));
+
+        // Update ETA and throughput
+        update_eta_throughput(context);
+        report_progress(progress_tx, context);
+    }
+
+    // Sync the device
+    context.sync_status = true;
+    file.sync_all()?;
+    context.sync_status = false;
+
+    // Don't close the file descriptor as it's owned by the context
+    std::mem::forget(file);
+
+    Ok(())
+}
+
+/// Verify `encrypted_zero_wipe`'s pseudo-random ciphertext, checking from
+/// `start_offset` bytes in. See `verify_random` for how resuming partway
+/// through is handled.
+fn verify_encrypted_zero(
+    context: &mut NwipeContext,
+    progress_tx: &ProgressSink,
+    cancel: &CancelFlag,
+    call_index: u32,
+    start_offset: u64,
+) -> Result<(), io::Error> {
+    // Open the device
+    let mut file = unsafe { File::from_raw_fd(context.device_fd) };
+
+    // Seek to where this call starts from
+    file.seek(SeekFrom::Start(start_offset))?;
+
+    let mut buffer = vec![0u8; NWIPE_KNOB_BUFSIZE];
+    let mut expected = vec![0u8; NWIPE_KNOB_BUFSIZE];
+
+    // Re-derive the same keystream generator from the key+nonce
+    // `write_encrypted_zero` recorded.
+    let mut keystream = EncryptedZeroKeystream::new(&context.prng_seed);
+
+    // Calculate the number of blocks to read
+    let block_count = (context.device_size + NWIPE_KNOB_BUFSIZE as u64 - 1) / NWIPE_KNOB_BUFSIZE as u64;
+    let first_block = start_offset / NWIPE_KNOB_BUFSIZE as u64;
+
+    for block in first_block..block_count {
+        // Check if we should abort
+        if cancel.is_set() {
+            return Err(io::Error::new(io::ErrorKind::Interrupted, "Verification interrupted by user"));
         }
 
-        if all_zeros || all_ones {
+        // Calculate the size of this block
+        let size = if block == block_count - 1 && context.device_size % NWIPE_KNOB_BUFSIZE as u64 != 0 {
+            (context.device_size % NWIPE_KNOB_BUFSIZE as u64) as usize
+        } else {
+            NWIPE_KNOB_BUFSIZE
+        };
+
+        // Read the block
+        file.read_exact(&mut buffer[0..size])?;
+
+        // Regenerate the expected keystream for this block
+        let block_offset = block * NWIPE_KNOB_BUFSIZE as u64;
+        keystream.fill_block(block_offset, &mut expected[0..size]);
+
+        // Verify the block, reporting the first mismatching byte's absolute
+        // device offset, exactly like `verify_pattern`/`verify_random`.
+        if let Some(i) = (0..size).find(|&i| buffer[i] != expected[i]) {
             return Err(io::Error::new(
                 io::ErrorKind::InvalidData,
                 format!(
-                    "Verification failed at block {}: data is {}",
-                    block,
-                    if all_zeros { "all zeros" } else { "all ones" }
+                    "Verification failed at offset {}: expected {:#04x} (regenerated from the recorded key+nonce), found {:#04x}",
+                    block_offset + i as u64,
+                    expected[i],
+                    buffer[i]
                 )
             ));
         }
@@ -619,9 +1511,11 @@ fn verify_random(context: &mut NwipeContext) -> Result<(), io::Error> {
         context.bytes_verified += size as u64;
         context.bytes_total += size as u64;
         context.round_percent = (block as f64 + 1.0) / block_count as f64 * 100.0;
+        checkpoint(context, call_index, ((block + 1) * NWIPE_KNOB_BUFSIZE as u64).min(context.device_size));
 
         // Update ETA and throughput
         update_eta_throughput(context);
+        report_progress(progress_tx, context);
     }
 
     // Don't close the file descriptor as it's owned by the context
@@ -13,6 +13,10 @@
 use clap::{Parser, ValueEnum};
 use std::path::PathBuf;
 
+use crate::logging::LogFormat;
+use crate::power::PowerAction;
+use crate::report::ReportFormat;
+
 /// Nwipe options structure
 #[derive(Debug, Clone)]
 pub struct NwipeOptions {
@@ -22,6 +26,15 @@ pub struct NwipeOptions {
     /// Exclude mounted partitions.
     pub exclude_mounted: bool,
 
+    /// Include devices that are normally excluded from the scan: mounted
+    /// disks, partitions, LVM/MD/ZFS members, and the disk holding the
+    /// running root filesystem. For advanced use only.
+    pub include_in_use: bool,
+
+    /// Remove any detected HPA/DCO before wiping so the full native
+    /// capacity of the media is covered, not just the reported capacity.
+    pub unhide_hpa: bool,
+
     /// Run without a GUI.
     pub nogui: bool,
 
@@ -34,15 +47,33 @@ pub struct NwipeOptions {
     /// Don't install signal handlers.
     pub nosignals: bool,
 
-    /// Power off system when wipe completed.
-    pub autopoweroff: bool,
+    /// What to do with the system once every selected device has been
+    /// wiped, if anything.
+    pub power_action: Option<PowerAction>,
+
+    /// Seconds to wait after the last wipe completes before performing
+    /// `power_action`.
+    pub power_delay: u64,
 
     /// Verbose output.
     pub verbose: bool,
 
+    /// The log output format: human-readable text, or one JSON object per line.
+    pub log_format: LogFormat,
+
+    /// Path to the log file, rotated once it exceeds the rotation limit.
+    pub log_file: PathBuf,
+
+    /// Also forward log events to the system journal/syslog.
+    pub syslog: bool,
+
     /// The PRNG algorithm to use.
     pub prng: String,
 
+    /// A file to draw PRNG seed material from instead of the entropy
+    /// source, for reproducible test runs.
+    pub seed_from: Option<PathBuf>,
+
     /// The wipe method to use.
     pub method: String,
 
@@ -52,6 +83,17 @@ pub struct NwipeOptions {
     /// Verify the wipe.
     pub verify: bool,
 
+    /// Ignore any on-disk progress journal and always start the method from
+    /// the beginning of the device, instead of resuming a prior run.
+    pub no_resume: bool,
+
+    /// Path to write a machine-readable completion report to, covering
+    /// every wiped device.
+    pub report: Option<PathBuf>,
+
+    /// The serialization format for `report`.
+    pub report_format: ReportFormat,
+
     /// Device names to wipe.
     pub device_names: Vec<String>,
 }
@@ -61,16 +103,26 @@ impl Default for NwipeOptions {
         Self {
             autonuke: false,
             exclude_mounted: false,
+            include_in_use: false,
+            unhide_hpa: false,
             nogui: false,
             modern_gui: true,  // Default to modern GUI
             nowait: false,
             nosignals: false,
-            autopoweroff: false,
+            power_action: None,
+            power_delay: 60,
             verbose: false,
+            log_format: LogFormat::Text,
+            log_file: PathBuf::from("/var/log/nwipe.log"),
+            syslog: false,
             prng: "isaac".to_string(),
+            seed_from: None,
             method: "ops2".to_string(),
             rounds: 1,
             verify: true,
+            no_resume: false,
+            report: None,
+            report_format: ReportFormat::Json,
             device_names: Vec::new(),
         }
     }
@@ -88,6 +140,16 @@ struct Args {
     #[clap(short = 'e', long)]
     exclude_mounted: bool,
 
+    /// Include devices normally excluded from the scan (mounted disks,
+    /// partitions, LVM/MD/ZFS members, the running root disk). Advanced use only.
+    #[clap(long)]
+    include_in_use: bool,
+
+    /// Remove any detected HPA/DCO before wiping so the full native
+    /// capacity of the media is covered
+    #[clap(long)]
+    unhide_hpa: bool,
+
     /// Run without a GUI
     #[clap(short = 'g', long)]
     nogui: bool,
@@ -104,18 +166,41 @@ struct Args {
     #[clap(short = 'l', long)]
     nosignals: bool,
 
-    /// Power off system when wipe completed
-    #[clap(short = 'p', long)]
-    autopoweroff: bool,
+    /// What to do once every selected device has been wiped: "poweroff",
+    /// "reboot", or "halt"
+    #[clap(short = 'p', long, value_enum)]
+    power_action: Option<PowerAction>,
+
+    /// Seconds to wait after the last wipe completes before performing
+    /// --power-action
+    #[clap(long, default_value_t = 60)]
+    power_delay: u64,
 
     /// Verbose output
     #[clap(short = 'v', long)]
     verbose: bool,
 
+    /// The log output format: "text" or "json"
+    #[clap(long, value_enum, default_value = "text")]
+    log_format: LogFormat,
+
+    /// Path to the log file, rotated once it exceeds the rotation limit
+    #[clap(long, default_value = "/var/log/nwipe.log")]
+    log_file: PathBuf,
+
+    /// Also forward log events to the system journal/syslog
+    #[clap(long)]
+    syslog: bool,
+
     /// The PRNG algorithm to use
     #[clap(short = 'P', long, default_value = "isaac")]
     prng: String,
 
+    /// Draw PRNG seed material from this file instead of the entropy
+    /// source, for reproducible test runs
+    #[clap(long)]
+    seed_from: Option<PathBuf>,
+
     /// The wipe method to use
     #[clap(short = 'm', long, default_value = "ops2")]
     method: String,
@@ -128,6 +213,20 @@ struct Args {
     #[clap(short = 'V', long)]
     verify: bool,
 
+    /// Ignore any on-disk progress journal and always start from the
+    /// beginning of the device, instead of resuming a prior interrupted run
+    #[clap(long)]
+    no_resume: bool,
+
+    /// Write a machine-readable completion report covering every wiped
+    /// device to this path
+    #[clap(long)]
+    report: Option<PathBuf>,
+
+    /// The serialization format for --report: "json" or "ron"
+    #[clap(long, value_enum, default_value = "json")]
+    report_format: ReportFormat,
+
     /// Device names to wipe
     #[clap(value_name = "DEVICE")]
     device_names: Vec<String>,
@@ -140,16 +239,26 @@ pub fn parse_options() -> NwipeOptions {
     NwipeOptions {
         autonuke: args.autonuke,
         exclude_mounted: args.exclude_mounted,
+        include_in_use: args.include_in_use,
+        unhide_hpa: args.unhide_hpa,
         nogui: args.nogui,
         modern_gui: !args.traditional_ui && !args.nogui, // Use modern GUI if not traditional UI and not nogui
         nowait: args.nowait,
         nosignals: args.nosignals,
-        autopoweroff: args.autopoweroff,
+        power_action: args.power_action,
+        power_delay: args.power_delay,
         verbose: args.verbose,
+        log_format: args.log_format,
+        log_file: args.log_file,
+        syslog: args.syslog,
         prng: args.prng,
+        seed_from: args.seed_from,
         method: args.method,
         rounds: args.rounds,
         verify: args.verify,
+        no_resume: args.no_resume,
+        report: args.report,
+        report_format: args.report_format,
         device_names: args.device_names,
     }
 }
@@ -0,0 +1,72 @@
+/*
+ *  power.rs: Post-wipe power actions for nwipe.
+ *
+ *  Copyright Sebastiaan Koetsier (2025)
+ *
+ *  This program is free software; you can redistribute it and/or modify it under
+ *  the terms of the GNU General Public License as published by the Free Software
+ *  Foundation, version 2.
+ */
+
+use std::process::Command;
+use std::thread;
+use std::time::Duration;
+
+use nix::sys::reboot::{reboot, RebootMode};
+use nix::unistd::sync;
+
+use crate::logging::{nwipe_log, LogLevel};
+
+/// What to do with the system once every selected device has been wiped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum PowerAction {
+    /// Power off.
+    Poweroff,
+    /// Reboot.
+    Reboot,
+    /// Halt without cutting power.
+    Halt,
+}
+
+/// Sync all wiped devices' data to disk, wait `delay_secs`, then perform
+/// `action` via the `reboot(2)` syscall directly, so this works on a
+/// provisioning appliance without `shutdown`/systemd present. Falls back to
+/// the `shutdown` command only if the syscall itself is denied (e.g.
+/// missing `CAP_SYS_BOOT`).
+pub fn execute(action: PowerAction, delay_secs: u64) {
+    nwipe_log(LogLevel::Notice, &format!("Power action '{:?}' will run in {} seconds", action, delay_secs));
+
+    sync();
+
+    if delay_secs > 0 {
+        thread::sleep(Duration::from_secs(delay_secs));
+    }
+
+    let mode = match action {
+        PowerAction::Poweroff => RebootMode::RB_POWER_OFF,
+        PowerAction::Reboot => RebootMode::RB_AUTOBOOT,
+        PowerAction::Halt => RebootMode::RB_HALT_SYSTEM,
+    };
+
+    if let Err(e) = reboot(mode) {
+        nwipe_log(
+            LogLevel::Warning,
+            &format!("reboot(2) syscall failed ({}), falling back to the shutdown command", e),
+        );
+        fallback_shutdown(action);
+    }
+}
+
+/// Shell out to `shutdown` for systems where the direct syscall was denied.
+fn fallback_shutdown(action: PowerAction) {
+    let flag = match action {
+        PowerAction::Poweroff => "-P",
+        PowerAction::Reboot => "-r",
+        PowerAction::Halt => "-H",
+    };
+
+    let cmd = format!("shutdown {} now", flag);
+    if let Err(e) = Command::new("sh").arg("-c").arg(&cmd).output() {
+        nwipe_log(LogLevel::Error, &format!("Failed to run fallback power command '{}': {}", cmd, e));
+    }
+}
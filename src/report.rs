@@ -0,0 +1,139 @@
+/*
+ *  report.rs: Machine-readable completion reports for nwipe.
+ *
+ *  Copyright Sebastiaan Koetsier (2025)
+ *
+ *  This program is free software; you can redistribute it and/or modify it under
+ *  the terms of the GNU General Public License as published by the Free Software
+ *  Foundation, version 2.
+ */
+
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::context::NwipeContext;
+use crate::version::VERSION_STRING;
+
+/// The serialization format for `--report`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReportFormat {
+    /// A single `serde_json` document.
+    Json,
+    /// A single RON (Rusty Object Notation) document.
+    Ron,
+}
+
+impl Default for ReportFormat {
+    fn default() -> Self {
+        ReportFormat::Json
+    }
+}
+
+/// Host information recorded once per run, alongside the nwipe version that
+/// produced it, so a report is self-describing without cross-referencing
+/// the log.
+#[derive(Debug, Clone, Serialize)]
+pub struct RunInfo {
+    pub nwipe_version: String,
+    pub hostname: String,
+    pub os: String,
+    pub kernel: String,
+}
+
+impl RunInfo {
+    fn gather() -> Self {
+        let os_info = os_info::get();
+
+        Self {
+            nwipe_version: VERSION_STRING.to_string(),
+            hostname: sys_info::hostname().unwrap_or_default(),
+            os: format!("{} {}", os_info.os_type(), os_info.version()),
+            kernel: sys_info::os_release().unwrap_or_default(),
+        }
+    }
+}
+
+/// The structured, per-device record that makes up the bulk of a report.
+#[derive(Debug, Clone, Serialize)]
+pub struct DeviceReport {
+    pub device_name: String,
+    pub model_no: String,
+    pub serial_no: String,
+    pub firmware_rev: String,
+    pub wwn: String,
+    pub device_size: u64,
+    pub sector_size: u64,
+    pub block_size: i32,
+    pub method: String,
+    pub pass_count: i32,
+    pub round_count: i32,
+    pub prng: String,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub bytes_written: u64,
+    pub verify_requested: bool,
+    pub result: i32,
+    pub passed: bool,
+}
+
+impl DeviceReport {
+    fn from_context(context: &NwipeContext, method: &str) -> Self {
+        Self {
+            device_name: context.device_name.clone(),
+            model_no: context.identity.model_no.clone(),
+            serial_no: context.identity.serial_no.clone(),
+            firmware_rev: context.identity.firmware_rev.clone(),
+            wwn: context.identity.wwn.clone(),
+            device_size: context.device_size,
+            sector_size: context.device_sector_size,
+            block_size: context.device_block_size,
+            method: method.to_string(),
+            pass_count: context.pass_count,
+            round_count: context.round_count,
+            prng: context.prng.clone(),
+            start_time: context.start_time,
+            end_time: context.end_time,
+            bytes_written: context.bytes_written,
+            verify_requested: context.verify,
+            result: context.result,
+            passed: context.result == 0,
+        }
+    }
+}
+
+/// The top-level document written by `--report`: run metadata plus one
+/// `DeviceReport` per wiped device.
+#[derive(Debug, Clone, Serialize)]
+pub struct RunReport {
+    pub run: RunInfo,
+    pub devices: Vec<DeviceReport>,
+}
+
+/// Build the report for every device in `contexts[0..count]`, using
+/// `method` as the wipe method name for every device (each context's own
+/// `wipe_method` is not read).
+pub fn build_report(contexts: &[NwipeContext], count: usize, method: &str) -> RunReport {
+    RunReport {
+        run: RunInfo::gather(),
+        devices: contexts.iter().take(count).map(|c| DeviceReport::from_context(c, method)).collect(),
+    }
+}
+
+/// Serialize `report` in the requested format and write it to `path`.
+pub fn write_report(report: &RunReport, path: &Path, format: ReportFormat) -> io::Result<()> {
+    let serialized = match format {
+        ReportFormat::Json => serde_json::to_string_pretty(report)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("Failed to serialize report as JSON: {}", e)))?,
+        ReportFormat::Ron => ron::ser::to_string_pretty(report, ron::ser::PrettyConfig::default())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("Failed to serialize report as RON: {}", e)))?,
+    };
+
+    let mut file = OpenOptions::new().create(true).write(true).truncate(true).open(path)?;
+    file.write_all(serialized.as_bytes())?;
+    file.write_all(b"\n")?;
+
+    Ok(())
+}
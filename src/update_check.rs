@@ -0,0 +1,99 @@
+/*
+ *  update_check.rs: Background GitHub release check for nwipe.
+ *
+ *  Copyright Sebastiaan Koetsier (2025)
+ *
+ *  This program is free software; you can redistribute it and/or modify it under
+ *  the terms of the GNU General Public License as published by the Free Software
+ *  Foundation, version 2.
+ */
+
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+use serde::Deserialize;
+
+use crate::version;
+
+/// The GitHub releases API endpoint nwipe checks for new versions.
+const RELEASES_URL: &str = "https://api.github.com/repos/jskoetsier/nwipe/releases/latest";
+
+/// The outcome of a background update check, polled by the GUI thread.
+#[derive(Debug, Clone)]
+pub enum UpdateStatus {
+    /// The check is still in flight.
+    Checking,
+    /// The installed version is the latest available.
+    UpToDate,
+    /// A newer release was found.
+    UpdateAvailable { tag: String, notes: String, url: String },
+    /// The check could not be completed.
+    Failed(String),
+}
+
+/// Spawn a background thread that queries the GitHub releases API and
+/// compares the latest tag against the running version. The result is sent
+/// once over the returned channel; the caller polls it from `update()` so
+/// the network call never blocks the UI thread.
+pub fn spawn_update_check() -> Receiver<UpdateStatus> {
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        let result = check_for_update();
+        let _ = tx.send(result);
+    });
+
+    rx
+}
+
+/// The subset of GitHub's release object this check reads.
+#[derive(Debug, Deserialize)]
+struct Release {
+    tag_name: String,
+    #[serde(default)]
+    body: String,
+    #[serde(default)]
+    html_url: String,
+}
+
+/// Perform the blocking HTTP request and version comparison.
+fn check_for_update() -> UpdateStatus {
+    let body = match ureq::get(RELEASES_URL)
+        .set("User-Agent", "nwipe-update-checker")
+        .call()
+    {
+        Ok(response) => match response.into_string() {
+            Ok(body) => body,
+            Err(e) => return UpdateStatus::Failed(format!("Failed to read response: {}", e)),
+        },
+        Err(e) => return UpdateStatus::Failed(format!("Failed to contact GitHub: {}", e)),
+    };
+
+    let release: Release = match serde_json::from_str(&body) {
+        Ok(release) => release,
+        Err(e) => return UpdateStatus::Failed(format!("Failed to parse release response: {}", e)),
+    };
+
+    let latest = release.tag_name.trim_start_matches('v');
+    if is_newer(latest, version::VERSION) {
+        UpdateStatus::UpdateAvailable { tag: release.tag_name, notes: release.body, url: release.html_url }
+    } else {
+        UpdateStatus::UpToDate
+    }
+}
+
+/// Compare two `major.minor.patch` version strings, returning true if
+/// `candidate` is strictly newer than `current`. Unparsable components are
+/// treated as zero so a malformed tag never wins out over a real version.
+fn is_newer(candidate: &str, current: &str) -> bool {
+    parse_semver(candidate) > parse_semver(current)
+}
+
+fn parse_semver(version: &str) -> (u32, u32, u32) {
+    let mut parts = version.split('.').map(|part| part.parse::<u32>().unwrap_or(0));
+    (
+        parts.next().unwrap_or(0),
+        parts.next().unwrap_or(0),
+        parts.next().unwrap_or(0),
+    )
+}